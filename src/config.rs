@@ -0,0 +1,538 @@
+use crate::i18n::Lang;
+use crate::keymap::Keymap;
+use crate::platform::{self, SourceInfo};
+use crate::theme::{self, CustomTheme, IconSet, Theme};
+use crate::view::SortMode;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::Duration;
+use toml::Spanned;
+
+/// 用户配置，对应 `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// 界面语言：`zh` / `en` / `auto`
+    pub language: Lang,
+    /// 主题名称：内置主题或 `[themes.<name>]` 中定义的主题
+    pub theme: Option<Spanned<String>>,
+    /// 图标：`emoji` / `ascii`
+    pub icons: IconSet,
+    /// 自定义主题，如 `[themes.solarized]`
+    pub themes: BTreeMap<Spanned<String>, CustomTheme>,
+    /// 启用鼠标（会占用终端自身的文本选择）
+    pub mouse: bool,
+    pub view: ViewConfig,
+    pub delete: DeleteConfig,
+    pub scan: ScanConfig,
+    /// 来源 ID → 来源配置，如 `[sources.plist]`
+    pub sources: BTreeMap<Spanned<String>, SourceConfig>,
+    /// 动作名 → 按键列表，如 `quit = ["q", "ctrl+c"]`；单个按键也可直接写字符串
+    pub keys: BTreeMap<Spanned<String>, KeyList>,
+    /// 启动项名称 → 额外的日志文件，用于定义中没有声明日志的启动项（如自有 agent）
+    pub logs: BTreeMap<String, Vec<String>>,
+    /// 配置文件本身，用于把错误定位到行号
+    #[serde(skip)]
+    file: Option<ConfigFile>,
+}
+
+/// `[view]`：列表的默认排序与过滤
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ViewConfig {
+    pub sort: SortMode,
+    pub filter: String,
+    /// 过滤关键字按正则表达式匹配
+    pub regex: bool,
+    /// 按来源 / 目录分组展示
+    pub grouped: bool,
+    /// 只显示开机或登录时启动的项（隐藏定时、按需等 launchd 任务）
+    pub startup_only: bool,
+}
+
+/// `[delete]`：删除行为
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeleteConfig {
+    /// 删除前先把定义文件移入隔离目录，便于恢复
+    pub quarantine: bool,
+    /// 隔离目录，缺省为用户数据目录下的 `bootwatch/quarantine`
+    pub quarantine_dir: Option<String>,
+    /// 删除 / 禁用后默认同时结束正在运行的进程（确认弹窗中可切换）
+    pub terminate: bool,
+}
+
+/// `[scan]`：后台扫描
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanConfig {
+    /// 单个外部命令（`launchctl`、`osascript`、`reg` 等）的超时秒数，超时即结束该命令
+    pub timeout: u64,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig { timeout: 10 }
+    }
+}
+
+/// `[sources.<id>]`：单个来源的配置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SourceConfig {
+    /// 未设置时取来源的默认值（`SourceInfo::default_enabled`），由 `Config::source` 填入
+    pub enabled: Option<bool>,
+    /// 额外扫描的目录，支持 `~`
+    pub extra_dirs: Vec<String>,
+}
+
+/// 按键列表，兼容 `"q"` 与 `["q", "esc"]` 两种写法
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            KeyList::One(s) => vec![s.clone()],
+            KeyList::Many(v) => v.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ConfigFile {
+    path: PathBuf,
+    text: String,
+}
+
+/// 配置诊断信息，带文件与行号
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.path, self.line) {
+            (Some(path), Some(line)) => write!(f, "{}:{}: {}", path.display(), line, self.message),
+            (Some(path), None) => write!(f, "{}: {}", path.display(), self.message),
+            _ => f.write_str(&self.message),
+        }
+    }
+}
+
+/// 字节偏移对应的行号（从 1 开始）
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+impl Config {
+    /// 解析配置文本；失败时返回带行号的诊断
+    fn parse(path: PathBuf, text: String) -> Result<Config, Diagnostic> {
+        match toml::from_str::<Config>(&text) {
+            Ok(mut config) => {
+                config.file = Some(ConfigFile { path, text });
+                Ok(config)
+            }
+            Err(e) => Err(Diagnostic {
+                line: e.span().map(|span| line_of(&text, span.start)),
+                path: Some(path),
+                message: e.message().trim().to_string(),
+            }),
+        }
+    }
+
+    fn diagnostic(&self, span: Option<Range<usize>>, message: String) -> Diagnostic {
+        Diagnostic {
+            path: self.file.as_ref().map(|f| f.path.clone()),
+            line: self
+                .file
+                .as_ref()
+                .zip(span)
+                .map(|(f, span)| line_of(&f.text, span.start)),
+            message,
+        }
+    }
+
+    /// 检查解析阶段无法发现的问题：未知主题、未知来源等
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        if let Some(theme) = &self.theme {
+            let custom = self
+                .themes
+                .keys()
+                .any(|name| name.get_ref() == theme.get_ref());
+            if !custom && Theme::by_name(theme.get_ref()).is_none() {
+                let names: Vec<&str> = Theme::NAMES
+                    .iter()
+                    .copied()
+                    .chain(self.themes.keys().map(|name| name.get_ref().as_str()))
+                    .collect();
+                diagnostics.push(self.diagnostic(
+                    Some(theme.span()),
+                    format!(
+                        "未知主题 \"{}\"，可选: {}",
+                        theme.get_ref(),
+                        names.join(", ")
+                    ),
+                ));
+            }
+        }
+        for (name, custom) in &self.themes {
+            if let Err(e) = Theme::custom(custom) {
+                diagnostics.push(self.diagnostic(
+                    Some(name.span()),
+                    format!("主题 \"{}\": {}", name.get_ref(), e),
+                ));
+            }
+        }
+        if self.scan.timeout == 0 {
+            diagnostics.push(self.diagnostic(None, "[scan] timeout 为 0，将使用 1 秒".to_string()));
+        }
+        for (id, source) in &self.sources {
+            match platform::SOURCES.iter().find(|s| s.id == id.get_ref()) {
+                None => diagnostics.push(self.diagnostic(
+                    Some(id.span()),
+                    format!(
+                        "未知来源 \"{}\"，可选: {}",
+                        id.get_ref(),
+                        platform::SOURCES
+                            .iter()
+                            .map(|s| s.id)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                )),
+                Some(info) if !info.scans_dirs && !source.extra_dirs.is_empty() => diagnostics
+                    .push(self.diagnostic(
+                        Some(id.span()),
+                        format!("来源 \"{}\" 不扫描目录，extra_dirs 将被忽略", id.get_ref()),
+                    )),
+                Some(_) => {}
+            }
+        }
+        diagnostics
+    }
+
+    /// 由 `[keys]` 表生成键位，错误定位到对应动作所在行
+    pub fn keymap(&self) -> (Keymap, Vec<Diagnostic>) {
+        let overrides = self
+            .keys
+            .iter()
+            .map(|(name, keys)| (name.get_ref().clone(), keys.to_vec()))
+            .collect();
+        let (keymap, errors) = Keymap::from_overrides(&overrides);
+        let diagnostics = errors
+            .into_iter()
+            .map(|(action, message)| {
+                let span = self
+                    .keys
+                    .keys()
+                    .find(|name| *name.get_ref() == action)
+                    .map(|name| name.span());
+                self.diagnostic(span, message)
+            })
+            .collect();
+        (keymap, diagnostics)
+    }
+
+    /// 当前主题；未配置或名称无效时使用默认主题，设置了 `NO_COLOR` 时去掉颜色
+    pub fn theme(&self) -> Theme {
+        self.resolve_theme(theme::no_color_requested())
+    }
+
+    fn resolve_theme(&self, no_color: bool) -> Theme {
+        let theme = self
+            .theme
+            .as_ref()
+            .and_then(|name| {
+                match self
+                    .themes
+                    .iter()
+                    .find(|(n, _)| n.get_ref() == name.get_ref())
+                {
+                    Some((_, custom)) => Theme::custom(custom).ok(),
+                    None => Theme::by_name(name.get_ref()),
+                }
+            })
+            .unwrap_or_default()
+            .with_icons(self.icons);
+        if no_color {
+            theme.no_color()
+        } else {
+            theme
+        }
+    }
+
+    /// 指定来源的配置，未配置时为默认（按来源的 `default_enabled` 决定是否启用、无额外目录）
+    pub fn source(&self, info: &SourceInfo) -> SourceConfig {
+        let mut source = self
+            .sources
+            .iter()
+            .find(|(name, _)| name.get_ref() == info.id)
+            .map(|(_, source)| source.clone())
+            .unwrap_or_default();
+        source.enabled = Some(source.enabled.unwrap_or(info.default_enabled));
+        source
+    }
+
+    /// 外部命令超时，至少 1 秒
+    pub fn scan_timeout(&self) -> Duration {
+        Duration::from_secs(self.scan.timeout.max(1))
+    }
+
+    /// `[logs]` 中为该启动项配置的日志文件，支持 `~`
+    pub fn log_files(&self, label: &str) -> Vec<PathBuf> {
+        self.logs
+            .get(label)
+            .into_iter()
+            .flatten()
+            .map(|path| shellexpand::tilde(path).into_owned().into())
+            .collect()
+    }
+
+    /// 删除时使用的隔离目录；未开启隔离时为 `None`
+    pub fn quarantine_dir(&self) -> Option<PathBuf> {
+        if !self.delete.quarantine {
+            return None;
+        }
+        match &self.delete.quarantine_dir {
+            Some(dir) => Some(shellexpand::tilde(dir).into_owned().into()),
+            None => platform::quarantine::default_dir(),
+        }
+    }
+}
+
+/// 候选配置文件路径，按优先级排列：
+/// `~/.config/bootwatch/config.toml`，其次是平台配置目录
+/// （macOS `~/Library/Application Support`，Windows `%APPDATA%`）下的 `bootwatch/config.toml`
+pub fn config_paths() -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join(".config").join("bootwatch").join("config.toml"));
+    }
+    if let Some(dir) = dirs::config_dir() {
+        let path = dir.join("bootwatch").join("config.toml");
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// 加载配置。文件不存在时返回默认配置；读取、解析或校验失败时返回默认值与诊断信息，
+/// 由 TUI 展示而不是中断运行。
+pub fn load() -> (Config, Vec<Diagnostic>) {
+    let Some(path) = config_paths().into_iter().find(|p| p.exists()) else {
+        return (Config::default(), vec![]);
+    };
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            let diagnostic = Diagnostic {
+                path: Some(path),
+                line: None,
+                message: e.to_string(),
+            };
+            return (Config::default(), vec![diagnostic]);
+        }
+    };
+    match Config::parse(path, text) {
+        Ok(config) => {
+            let diagnostics = config.validate();
+            (config, diagnostics)
+        }
+        Err(diagnostic) => (Config::default(), vec![diagnostic]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Config, Diagnostic> {
+        Config::parse(PathBuf::from("config.toml"), text.to_string())
+    }
+
+    #[test]
+    fn keys_accept_string_or_list() {
+        let config = parse(
+            r#"
+            [keys]
+            quit = "ctrl+q"
+            move_down = ["j", "ctrl+n"]
+            "#,
+        )
+        .unwrap();
+        let (_, diagnostics) = config.keymap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let keys: Vec<_> = config.keys.values().map(KeyList::to_vec).collect();
+        assert!(keys.contains(&vec!["ctrl+q".to_string()]));
+        assert!(keys.contains(&vec!["j".to_string(), "ctrl+n".to_string()]));
+    }
+
+    #[test]
+    fn empty_config_is_default() {
+        let config = parse("").unwrap();
+        assert!(config.keys.is_empty());
+        assert_eq!(config.language, Lang::Zh);
+        assert_eq!(config.view.sort, SortMode::Scan);
+        assert!(config.quarantine_dir().is_none());
+        assert_eq!(config.scan_timeout(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn full_config() {
+        let config = parse(
+            r#"
+            language = "en"
+            theme = "dark"
+            mouse = true
+
+            [view]
+            sort = "name"
+            filter = "adobe"
+
+            [delete]
+            quarantine = true
+            quarantine_dir = "/tmp/q"
+
+            [scan]
+            timeout = 3
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.language, Lang::En);
+        assert!(config.mouse);
+        assert_eq!(config.view.sort, SortMode::Name);
+        assert_eq!(config.view.filter, "adobe");
+        assert_eq!(config.quarantine_dir(), Some(PathBuf::from("/tmp/q")));
+        assert_eq!(config.scan_timeout(), Duration::from_secs(3));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn syntax_error_reports_line() {
+        let err = parse("language = \"en\"\n\n[view]\nsort = \"sideways\"\n").unwrap_err();
+        assert_eq!(err.line, Some(4));
+        assert!(err.to_string().starts_with("config.toml:4: "), "{}", err);
+    }
+
+    #[test]
+    fn unknown_field_is_rejected() {
+        let err = parse("[delete]\nquarantin = true\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn unknown_theme_and_source_report_line() {
+        let config = parse("theme = \"neon\"\n\n[sources.nonexistent]\nenabled = false\n").unwrap();
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line, Some(1));
+        assert_eq!(diagnostics[1].line, Some(3));
+    }
+
+    #[test]
+    fn custom_theme_and_icons() {
+        let config = parse(
+            r#"
+            theme = "mine"
+            icons = "ascii"
+
+            [themes.mine]
+            base = "high-contrast"
+            error = "magenta"
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_empty());
+        let theme = config.resolve_theme(false);
+        assert_eq!(theme.error, ratatui::style::Color::Magenta);
+        assert_eq!(theme.icons.ok, "[ok]");
+        let plain = config.resolve_theme(true);
+        assert_eq!(plain.error, ratatui::style::Color::Reset);
+    }
+
+    #[test]
+    fn log_files_by_item_name() {
+        let config = parse(
+            r#"
+            [logs]
+            "com.example.agent" = ["/var/log/agent.log", "/tmp/agent.err"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.log_files("com.example.agent"),
+            [
+                PathBuf::from("/var/log/agent.log"),
+                PathBuf::from("/tmp/agent.err")
+            ]
+        );
+        assert!(config.log_files("other").is_empty());
+    }
+
+    #[test]
+    fn invalid_custom_theme_reports_line() {
+        let config = parse("[themes.bad]\nheader = \"nope\"\n").unwrap();
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(1));
+    }
+
+    #[test]
+    fn keymap_errors_report_line() {
+        let config = parse("[keys]\nquit = \"q\"\nrefresh = \"hyperspace\"\n").unwrap();
+        let (_, diagnostics) = config.keymap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(3));
+    }
+
+    const ON: SourceInfo = SourceInfo {
+        id: "on",
+        label: "On",
+        scans_dirs: true,
+        default_enabled: true,
+    };
+    const OFF: SourceInfo = SourceInfo {
+        id: "off",
+        label: "Off",
+        scans_dirs: true,
+        default_enabled: false,
+    };
+
+    #[test]
+    fn source_defaults_to_enabled() {
+        let config = parse("").unwrap();
+        let source = config.source(&ON);
+        assert_eq!(source.enabled, Some(true));
+        assert!(source.extra_dirs.is_empty());
+        assert_eq!(config.source(&OFF).enabled, Some(false));
+    }
+
+    #[test]
+    fn extra_dirs_keep_source_default() {
+        // 只追加目录不会启用默认关闭的来源
+        let config =
+            parse("[sources.off]\nextra_dirs = [\"/x\"]\n[sources.on]\nextra_dirs = []\n").unwrap();
+        let off = config.source(&OFF);
+        assert_eq!(off.enabled, Some(false));
+        assert_eq!(off.extra_dirs, ["/x"]);
+        assert_eq!(config.source(&ON).enabled, Some(true));
+        let config =
+            parse("[sources.off]\nenabled = true\n[sources.on]\nenabled = false\n").unwrap();
+        assert_eq!(config.source(&OFF).enabled, Some(true));
+        assert_eq!(config.source(&ON).enabled, Some(false));
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Deserializer};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Lang {
+    /// 按语言在两段静态文本中选择
+    pub fn pick(self, zh: &'static str, en: &'static str) -> &'static str {
+        match self {
+            Lang::Zh => zh,
+            Lang::En => en,
+        }
+    }
+
+    /// 根据 `LC_ALL` / `LC_MESSAGES` / `LANG` 推断语言，中文环境用中文，其余用英文
+    pub fn from_env() -> Lang {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.is_empty())
+            .unwrap_or_default();
+        if locale.to_ascii_lowercase().starts_with("zh") {
+            Lang::Zh
+        } else {
+            Lang::En
+        }
+    }
+}
+
+/// 配置中接受 `"zh"`、`"en"` 与 `"auto"`（按环境变量推断）
+impl<'de> Deserialize<'de> for Lang {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.to_ascii_lowercase().as_str() {
+            "zh" | "zh-cn" | "chinese" => Ok(Lang::Zh),
+            "en" | "english" => Ok(Lang::En),
+            "auto" => Ok(Lang::from_env()),
+            _ => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&s),
+                &"\"zh\", \"en\" or \"auto\"",
+            )),
+        }
+    }
+}
+
+/// 按语言格式化文本：`tr!(lang, "中文 {}", "English {}", arg)`，两种格式串共享参数
+macro_rules! tr {
+    ($lang:expr, $zh:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $lang {
+            $crate::i18n::Lang::Zh => format!($zh $(, $arg)*),
+            $crate::i18n::Lang::En => format!($en $(, $arg)*),
+        }
+    };
+}
@@ -0,0 +1,818 @@
+use crate::i18n::Lang;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// TUI 中可绑定按键的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    MoveDown,
+    MoveUp,
+    MoveTop,
+    MoveBottom,
+    Delete,
+    Disable,
+    Open,
+    Logs,
+    ToggleMark,
+    MarkAll,
+    InvertMarks,
+    Refresh,
+    ToggleLayout,
+    Columns,
+    Details,
+    CycleSort,
+    ToggleGroups,
+    ToggleStartupOnly,
+    ToggleFold,
+    Collapse,
+    Expand,
+    NextTab,
+    PrevTab,
+    Search,
+    NextMatch,
+    PrevMatch,
+    ClearFilter,
+    Diagnostics,
+    Help,
+    Confirm,
+    ToggleTerminate,
+    MoveColumnDown,
+    MoveColumnUp,
+}
+
+/// 帮助弹窗中动作的分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Navigation,
+    Selection,
+    Operation,
+    View,
+    Search,
+    General,
+}
+
+impl Category {
+    /// 帮助弹窗中的展示顺序
+    pub const ALL: &'static [Category] = &[
+        Category::Navigation,
+        Category::Selection,
+        Category::Operation,
+        Category::View,
+        Category::Search,
+        Category::General,
+    ];
+
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            Category::Navigation => lang.pick("移动", "Navigation"),
+            Category::Selection => lang.pick("选择", "Selection"),
+            Category::Operation => lang.pick("操作", "Operations"),
+            Category::View => lang.pick("视图", "View"),
+            Category::Search => lang.pick("搜索", "Search"),
+            Category::General => lang.pick("通用", "General"),
+        }
+    }
+}
+
+impl Action {
+    /// 所有动作，顺序即帮助栏中的展示顺序
+    pub const ALL: &'static [Action] = &[
+        Action::MoveDown,
+        Action::MoveUp,
+        Action::MoveTop,
+        Action::MoveBottom,
+        Action::Delete,
+        Action::Disable,
+        Action::Open,
+        Action::Logs,
+        Action::ToggleMark,
+        Action::MarkAll,
+        Action::InvertMarks,
+        Action::Refresh,
+        Action::ToggleLayout,
+        Action::Columns,
+        Action::Details,
+        Action::CycleSort,
+        Action::ToggleGroups,
+        Action::ToggleStartupOnly,
+        Action::ToggleFold,
+        Action::Collapse,
+        Action::Expand,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::Search,
+        Action::NextMatch,
+        Action::PrevMatch,
+        Action::ClearFilter,
+        Action::Diagnostics,
+        Action::Help,
+        Action::Confirm,
+        Action::ToggleTerminate,
+        Action::MoveColumnDown,
+        Action::MoveColumnUp,
+        Action::Quit,
+    ];
+
+    /// 配置文件 `[keys]` 表中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::MoveDown => "move_down",
+            Action::MoveUp => "move_up",
+            Action::MoveTop => "move_top",
+            Action::MoveBottom => "move_bottom",
+            Action::Delete => "delete",
+            Action::Disable => "disable",
+            Action::Open => "open",
+            Action::Logs => "logs",
+            Action::ToggleMark => "toggle_mark",
+            Action::MarkAll => "mark_all",
+            Action::InvertMarks => "invert_marks",
+            Action::Refresh => "refresh",
+            Action::ToggleLayout => "toggle_layout",
+            Action::Columns => "columns",
+            Action::Details => "details",
+            Action::CycleSort => "cycle_sort",
+            Action::ToggleGroups => "toggle_groups",
+            Action::ToggleStartupOnly => "toggle_startup_only",
+            Action::ToggleFold => "toggle_fold",
+            Action::Collapse => "collapse",
+            Action::Expand => "expand",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::Search => "search",
+            Action::NextMatch => "next_match",
+            Action::PrevMatch => "prev_match",
+            Action::ClearFilter => "clear_filter",
+            Action::Diagnostics => "diagnostics",
+            Action::Help => "help",
+            Action::Confirm => "confirm",
+            Action::ToggleTerminate => "toggle_terminate",
+            Action::MoveColumnDown => "move_column_down",
+            Action::MoveColumnUp => "move_column_up",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|a| a.name() == name)
+    }
+
+    /// 帮助栏中的简短说明
+    pub fn describe(self, lang: Lang) -> &'static str {
+        match self {
+            Action::Quit => lang.pick("退出", "quit"),
+            Action::MoveDown => lang.pick("下移", "down"),
+            Action::MoveUp => lang.pick("上移", "up"),
+            Action::MoveTop => lang.pick("首项", "top"),
+            Action::MoveBottom => lang.pick("末项", "bottom"),
+            Action::Delete => lang.pick("删除", "delete"),
+            Action::Disable => lang.pick("禁用", "disable"),
+            Action::Open => lang.pick("编辑", "edit"),
+            Action::Logs => lang.pick("日志", "logs"),
+            Action::ToggleMark => lang.pick("标记", "mark"),
+            Action::MarkAll => lang.pick("全部标记", "mark all"),
+            Action::InvertMarks => lang.pick("反选", "invert marks"),
+            Action::Refresh => lang.pick("刷新", "refresh"),
+            Action::ToggleLayout => lang.pick("表格/列表", "table/list"),
+            Action::Columns => lang.pick("列设置", "columns"),
+            Action::Details => lang.pick("详情", "details"),
+            Action::CycleSort => lang.pick("排序", "sort"),
+            Action::ToggleGroups => lang.pick("分组", "group"),
+            Action::ToggleStartupOnly => lang.pick("仅开机启动", "startup only"),
+            Action::ToggleFold => lang.pick("折叠/展开", "fold"),
+            Action::Collapse => lang.pick("折叠", "collapse"),
+            Action::Expand => lang.pick("展开", "expand"),
+            Action::NextTab => lang.pick("下一标签页", "next tab"),
+            Action::PrevTab => lang.pick("上一标签页", "prev tab"),
+            Action::Search => lang.pick("搜索", "search"),
+            Action::NextMatch => lang.pick("下一匹配", "next match"),
+            Action::PrevMatch => lang.pick("上一匹配", "prev match"),
+            Action::ClearFilter => lang.pick("清除过滤", "clear filter"),
+            Action::Diagnostics => lang.pick("诊断", "diagnostics"),
+            Action::Help => lang.pick("帮助", "help"),
+            Action::Confirm => lang.pick("确认", "confirm"),
+            Action::ToggleTerminate => lang.pick("结束进程", "terminate"),
+            Action::MoveColumnDown => lang.pick("列下移", "column down"),
+            Action::MoveColumnUp => lang.pick("列上移", "column up"),
+        }
+    }
+
+    pub fn category(self) -> Category {
+        match self {
+            Action::MoveDown
+            | Action::MoveUp
+            | Action::MoveTop
+            | Action::MoveBottom
+            | Action::NextTab
+            | Action::PrevTab => Category::Navigation,
+            Action::ToggleMark | Action::MarkAll | Action::InvertMarks => Category::Selection,
+            Action::Delete
+            | Action::Disable
+            | Action::Open
+            | Action::Refresh
+            | Action::Confirm
+            | Action::ToggleTerminate => Category::Operation,
+            Action::ToggleLayout
+            | Action::Columns
+            | Action::Details
+            | Action::Logs
+            | Action::CycleSort
+            | Action::ToggleGroups
+            | Action::ToggleStartupOnly
+            | Action::ToggleFold
+            | Action::Collapse
+            | Action::Expand
+            | Action::MoveColumnDown
+            | Action::MoveColumnUp => Category::View,
+            Action::Search | Action::NextMatch | Action::PrevMatch | Action::ClearFilter => {
+                Category::Search
+            }
+            Action::Diagnostics | Action::Help | Action::Quit => Category::General,
+        }
+    }
+
+    /// 帮助弹窗中的说明，比帮助栏的简短说明更完整
+    pub fn help(self, lang: Lang) -> &'static str {
+        match self {
+            Action::Quit => lang.pick("退出程序", "Quit"),
+            Action::MoveDown => lang.pick("选中下一行", "Select the next row"),
+            Action::MoveUp => lang.pick("选中上一行", "Select the previous row"),
+            Action::MoveTop => lang.pick("跳到第一行", "Jump to the first row"),
+            Action::MoveBottom => lang.pick("跳到最后一行", "Jump to the last row"),
+            Action::Delete => lang.pick(
+                "删除选中项（有标记时删除全部标记项）",
+                "Delete the selection, or all marked items",
+            ),
+            Action::Disable => lang.pick(
+                "禁用选中项（有标记时禁用全部标记项），保留定义",
+                "Disable the selection, or all marked items, keeping definitions",
+            ),
+            Action::Open => lang.pick(
+                "在 $VISUAL / $EDITOR 中编辑定义文件，保存后校验；注册表值导出为 .reg 编辑后可导入",
+                "Edit the definition in $VISUAL / $EDITOR and validate it; registry values round-trip through a .reg file",
+            ),
+            Action::Logs => lang.pick(
+                "查看选中项的日志：实时跟随，可搜索",
+                "View the selection's log output, following live with search",
+            ),
+            Action::ToggleMark => lang.pick(
+                "标记 / 取消标记选中项；在分组标题上作用于整组",
+                "Mark or unmark the selection; on a group header, the whole group",
+            ),
+            Action::MarkAll => lang.pick("标记全部可见项", "Mark every visible item"),
+            Action::InvertMarks => lang.pick("反选可见项", "Invert marks on visible items"),
+            Action::Refresh => lang.pick("重新扫描启动项", "Rescan startup items"),
+            Action::ToggleLayout => {
+                lang.pick("在表格与两行列表之间切换", "Switch between table and list")
+            }
+            Action::Columns => lang.pick(
+                "设置表格列的显示、宽度与顺序",
+                "Choose table columns, widths and order",
+            ),
+            Action::Details => lang.pick("显示 / 隐藏详情面板", "Show or hide the detail pane"),
+            Action::CycleSort => lang.pick("切换排序方式", "Cycle the sort order"),
+            Action::ToggleGroups => {
+                lang.pick("按来源 / 目录分组显示", "Group by source and directory")
+            }
+            Action::ToggleStartupOnly => lang.pick(
+                "只显示开机或登录时启动的项，隐藏定时、路径触发与按需运行的 launchd 任务",
+                "Show only items launched at boot or login, hiding periodic, path-triggered and on-demand launchd jobs",
+            ),
+            Action::ToggleFold => lang.pick("折叠 / 展开当前分组", "Fold or unfold the group"),
+            Action::Collapse => lang.pick("折叠当前分组", "Collapse the group"),
+            Action::Expand => lang.pick("展开当前分组", "Expand the group"),
+            Action::NextTab => lang.pick("下一个来源标签页", "Next source tab"),
+            Action::PrevTab => lang.pick("上一个来源标签页", "Previous source tab"),
+            Action::Search => {
+                lang.pick("按名称、路径或命令过滤", "Filter by name, path or command")
+            }
+            Action::NextMatch => lang.pick("跳到下一个匹配项", "Jump to the next match"),
+            Action::PrevMatch => lang.pick("跳到上一个匹配项", "Jump to the previous match"),
+            Action::ClearFilter => lang.pick(
+                "扫描中取消扫描；否则清除过滤（没有过滤时退出）",
+                "Cancel a running scan, else clear the filter (quits when there is none)",
+            ),
+            Action::Diagnostics => lang.pick("查看配置诊断", "Show config diagnostics"),
+            Action::Help => lang.pick("显示本帮助", "Show this help"),
+            Action::Confirm => {
+                lang.pick("在确认弹窗中执行操作", "Confirm in the confirmation popup")
+            }
+            Action::ToggleTerminate => lang.pick(
+                "在确认弹窗中切换是否同时结束正在运行的进程",
+                "In the confirmation popup, toggle terminating running instances",
+            ),
+            Action::MoveColumnDown => lang.pick(
+                "在列设置中把选中列后移一位",
+                "In the columns popup, move the column one place later",
+            ),
+            Action::MoveColumnUp => lang.pick(
+                "在列设置中把选中列前移一位",
+                "In the columns popup, move the column one place earlier",
+            ),
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::MoveDown => &["j", "down"],
+            Action::MoveUp => &["k", "up"],
+            Action::MoveTop => &["g", "home"],
+            Action::MoveBottom => &["G", "end"],
+            Action::Delete => &["d", "delete"],
+            Action::Disable => &["x"],
+            Action::Open => &["o"],
+            Action::Logs => &["l"],
+            Action::ToggleMark => &["space"],
+            Action::MarkAll => &["a"],
+            Action::InvertMarks => &["i"],
+            Action::Refresh => &["r"],
+            Action::ToggleLayout => &["t"],
+            Action::Columns => &["c"],
+            Action::Details => &["p"],
+            Action::CycleSort => &["s"],
+            Action::ToggleGroups => &["v"],
+            Action::ToggleStartupOnly => &["b"],
+            Action::ToggleFold => &["z"],
+            Action::Collapse => &["h", "left"],
+            Action::Expand => &["right"],
+            Action::NextTab => &["tab"],
+            Action::PrevTab => &["shift+tab"],
+            Action::Search => &["/"],
+            Action::NextMatch => &["n"],
+            Action::PrevMatch => &["N"],
+            Action::ClearFilter => &["esc"],
+            Action::Diagnostics => &["!"],
+            Action::Help => &["?"],
+            Action::Confirm => &["y", "Y", "enter"],
+            Action::ToggleTerminate => &["t"],
+            Action::MoveColumnDown => &["J"],
+            Action::MoveColumnUp => &["K"],
+        }
+    }
+
+    /// 是否只在弹窗（确认、列设置）中生效（与普通模式的按键互不冲突）
+    fn is_modal(self) -> bool {
+        matches!(
+            self,
+            Action::Confirm
+                | Action::ToggleTerminate
+                | Action::MoveColumnDown
+                | Action::MoveColumnUp
+        )
+    }
+
+    /// 是否在列设置弹窗中生效；其中普通动作先于调整列顺序的动作处理
+    fn in_column_popup(self) -> bool {
+        matches!(
+            self,
+            Action::MoveDown
+                | Action::MoveUp
+                | Action::ToggleMark
+                | Action::Collapse
+                | Action::Expand
+                | Action::MoveColumnDown
+                | Action::MoveColumnUp
+        )
+    }
+}
+
+/// 一个按键组合：键码 + 修饰键
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// 解析按键描述，如 `j`、`ctrl+d`、`alt-x`、`shift+tab`、`f5`、`space`。
+    ///
+    /// 修饰键可用 `+` 或 `-` 连接；单个字符按字面处理，因此 `-`、`+` 本身也可绑定。
+    pub fn parse(spec: &str) -> Result<KeyBinding, String> {
+        let mut rest = spec.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let stripped = [
+                ("ctrl", KeyModifiers::CONTROL),
+                ("c", KeyModifiers::CONTROL),
+                ("alt", KeyModifiers::ALT),
+                ("m", KeyModifiers::ALT),
+                ("shift", KeyModifiers::SHIFT),
+                ("s", KeyModifiers::SHIFT),
+            ]
+            .iter()
+            .find_map(|(prefix, m)| {
+                let sep = lower.strip_prefix(prefix)?.chars().next()?;
+                // 前缀之后必须是分隔符且后面还有内容，避免把 `s`、`c-` 等误判为修饰键
+                ((sep == '+' || sep == '-') && lower.len() > prefix.len() + 1)
+                    .then_some((prefix.len() + 1, *m))
+            });
+            match stripped {
+                Some((len, m)) => {
+                    modifiers |= m;
+                    rest = &rest[len..];
+                }
+                None => break,
+            }
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "" => return Err(format!("按键为空: \"{}\"", spec)),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "delete" | "del" => KeyCode::Delete,
+            "backspace" => KeyCode::Backspace,
+            "insert" | "ins" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            lower => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n @ 1..=24) => KeyCode::F(n),
+                        _ => return Err(format!("无法识别的按键: \"{}\"", spec)),
+                    },
+                }
+            }
+        };
+        Ok(KeyBinding { code, modifiers }.normalized())
+    }
+
+    /// 归一化：字符键的大小写已体现 Shift，`shift+tab` 统一为 BackTab
+    fn normalized(mut self) -> KeyBinding {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.modifiers.remove(KeyModifiers::SHIFT);
+                self.code = KeyCode::Char(c.to_ascii_uppercase());
+            }
+            KeyCode::Tab if self.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.modifiers.remove(KeyModifiers::SHIFT);
+                self.code = KeyCode::BackTab;
+            }
+            KeyCode::BackTab => self.modifiers.remove(KeyModifiers::SHIFT),
+            _ => {}
+        }
+        self
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // 只比较 Ctrl/Alt/Shift，忽略 Super 等终端未必上报的修饰键
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT;
+        let pressed = KeyBinding {
+            code: key.code,
+            modifiers: key.modifiers & relevant,
+        }
+        .normalized();
+        pressed == *self
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("Shift+")?;
+        }
+        match self.code {
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::Insert => f.write_str("Ins"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// 动作到按键的映射
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| {
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .map(|spec| KeyBinding::parse(spec).expect("默认按键必须合法"))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// 在默认键位基础上应用配置文件 `[keys]` 表中的覆盖。
+    ///
+    /// 配置中出现的动作整体替换其默认按键；无法识别的动作名或按键、
+    /// 以及同一按键被多个动作占用时，以 `(动作名, 错误信息)` 记录到返回的错误列表中。
+    pub fn from_overrides(
+        overrides: &BTreeMap<String, Vec<String>>,
+    ) -> (Keymap, Vec<(String, String)>) {
+        let mut keymap = Keymap::default();
+        let mut errors = vec![];
+
+        for (name, specs) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                errors.push((name.clone(), format!("[keys] 未知动作: \"{}\"", name)));
+                continue;
+            };
+            let mut keys = vec![];
+            for spec in specs {
+                match KeyBinding::parse(spec) {
+                    Ok(key) => keys.push(key),
+                    Err(e) => errors.push((name.clone(), format!("[keys] {}: {}", name, e))),
+                }
+            }
+            keymap.bindings.insert(action, keys);
+        }
+
+        // 冲突归到配置中出现的那个动作上，便于定位到对应行
+        for (a, b, key) in keymap.conflicts() {
+            let blamed = if overrides.contains_key(b.name()) {
+                b
+            } else {
+                a
+            };
+            errors.push((
+                blamed.name().to_string(),
+                format!(
+                    "[keys] 按键 {} 同时绑定到 {} 与 {}",
+                    key,
+                    a.name(),
+                    b.name()
+                ),
+            ));
+        }
+        (keymap, errors)
+    }
+
+    /// 同一作用域（普通模式 / 弹窗 / 列设置弹窗）内被多个动作绑定的按键
+    fn conflicts(&self) -> Vec<(Action, Action, KeyBinding)> {
+        let entries: Vec<(Action, KeyBinding)> = self
+            .bindings
+            .iter()
+            .flat_map(|(&a, keys)| keys.iter().map(move |&k| (a, k)))
+            .collect();
+        let mut conflicts = vec![];
+        for (i, &(a, k)) in entries.iter().enumerate() {
+            for &(b, other) in &entries[i + 1..] {
+                let same_scope =
+                    a.is_modal() == b.is_modal() || (a.in_column_popup() && b.in_column_popup());
+                if a != b && k == other && same_scope {
+                    conflicts.push((a, b, k));
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 判断按键事件是否触发指定动作
+    pub fn is(&self, action: Action, key: &KeyEvent) -> bool {
+        self.keys(action).iter().any(|k| k.matches(key))
+    }
+
+    /// 查找普通模式下按键对应的动作（弹窗专用动作不参与）
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        Action::ALL
+            .iter()
+            .copied()
+            .filter(|a| !a.is_modal())
+            .find(|&a| self.is(a, key))
+    }
+
+    /// 动作的按键描述，如 `j/↓`；未绑定时返回 `-`
+    pub fn describe_keys(&self, action: Action) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "-".to_string();
+        }
+        keys.iter()
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// 帮助弹窗的内容：按分类列出每个动作的当前按键与说明
+    pub fn help_sections(&self, lang: Lang) -> Vec<(Category, Vec<(String, &'static str)>)> {
+        Category::ALL
+            .iter()
+            .map(|&category| {
+                let entries = Action::ALL
+                    .iter()
+                    .filter(|a| a.category() == category)
+                    .map(|&a| (self.describe_keys(a), a.help(lang)))
+                    .collect();
+                (category, entries)
+            })
+            .collect()
+    }
+
+    /// 底部帮助栏文本，由当前键位生成
+    pub fn help_line(&self, actions: &[Action], lang: Lang) -> String {
+        let parts: Vec<String> = actions
+            .iter()
+            .filter(|&&a| !self.keys(a).is_empty())
+            .map(|&a| format!("{} {}", self.describe_keys(a), a.describe(lang)))
+            .collect();
+        format!(" {} ", parts.join(" · "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEventKind;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        let mut key = KeyEvent::new(code, modifiers);
+        key.kind = KeyEventKind::Press;
+        key
+    }
+
+    #[test]
+    fn parse_plain_and_named_keys() {
+        assert_eq!(KeyBinding::parse("j").unwrap().code, KeyCode::Char('j'));
+        assert_eq!(KeyBinding::parse("Esc").unwrap().code, KeyCode::Esc);
+        assert_eq!(KeyBinding::parse("space").unwrap().code, KeyCode::Char(' '));
+        assert_eq!(KeyBinding::parse("F5").unwrap().code, KeyCode::F(5));
+        assert_eq!(KeyBinding::parse("-").unwrap().code, KeyCode::Char('-'));
+        assert_eq!(KeyBinding::parse("s").unwrap().code, KeyCode::Char('s'));
+    }
+
+    #[test]
+    fn parse_modifiers() {
+        let k = KeyBinding::parse("ctrl+d").unwrap();
+        assert_eq!(k.code, KeyCode::Char('d'));
+        assert_eq!(k.modifiers, KeyModifiers::CONTROL);
+
+        let k = KeyBinding::parse("C-alt-x").unwrap();
+        assert_eq!(k.modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        // shift+字母归一化为大写字母，shift+tab 归一化为 BackTab
+        assert_eq!(
+            KeyBinding::parse("shift+g").unwrap(),
+            KeyBinding::parse("G").unwrap()
+        );
+        assert_eq!(
+            KeyBinding::parse("shift+tab").unwrap().code,
+            KeyCode::BackTab
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown() {
+        assert!(KeyBinding::parse("").is_err());
+        assert!(KeyBinding::parse("ctrl+").is_err());
+        assert!(KeyBinding::parse("hyper").is_err());
+        assert!(KeyBinding::parse("f99").is_err());
+    }
+
+    #[test]
+    fn matches_ignores_shift_on_chars() {
+        let k = KeyBinding::parse("G").unwrap();
+        assert!(k.matches(&press(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+        assert!(k.matches(&press(KeyCode::Char('G'), KeyModifiers::NONE)));
+        assert!(!k.matches(&press(KeyCode::Char('g'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn matches_requires_modifiers() {
+        let k = KeyBinding::parse("ctrl+n").unwrap();
+        assert!(k.matches(&press(KeyCode::Char('n'), KeyModifiers::CONTROL)));
+        assert!(!k.matches(&press(KeyCode::Char('n'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("quit".to_string(), vec!["ctrl+q".to_string()]);
+        let (keymap, errors) = Keymap::from_overrides(&overrides);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('q'), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn overrides_report_errors_and_conflicts() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("frobnicate".to_string(), vec!["x".to_string()]);
+        overrides.insert("refresh".to_string(), vec!["j".to_string()]);
+        let (_, errors) = Keymap::from_overrides(&overrides);
+        assert!(errors.iter().any(|(name, _)| name == "frobnicate"));
+        assert!(errors
+            .iter()
+            .any(|(name, e)| name == "refresh" && e.contains("move_down")));
+    }
+
+    #[test]
+    fn confirm_keys_do_not_conflict_with_normal_mode() {
+        // enter 同时用于确认弹窗，不应视为冲突
+        let mut overrides = BTreeMap::new();
+        overrides.insert("refresh".to_string(), vec!["enter".to_string()]);
+        let (_, errors) = Keymap::from_overrides(&overrides);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn column_moves_are_rebindable_popup_keys() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("move_column_down".to_string(), vec!["ctrl+j".to_string()]);
+        overrides.insert("move_column_up".to_string(), vec!["y".to_string()]);
+        let (keymap, errors) = Keymap::from_overrides(&overrides);
+        // 与确认弹窗同属弹窗作用域，y 与 confirm 冲突
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(errors[0].0, "move_column_up");
+        let key = press(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert!(keymap.is(Action::MoveColumnDown, &key));
+        // 弹窗动作不参与普通模式
+        assert_eq!(keymap.action_for(&key), None);
+        assert_eq!(
+            Keymap::default().action_for(&press(KeyCode::Char('J'), KeyModifiers::SHIFT)),
+            None
+        );
+    }
+
+    #[test]
+    fn column_moves_conflict_with_column_popup_keys() {
+        // 列设置弹窗先处理 move_down，j 永远不会触发 move_column_down
+        let mut overrides = BTreeMap::new();
+        overrides.insert("move_column_down".to_string(), vec!["j".to_string()]);
+        let (_, errors) = Keymap::from_overrides(&overrides);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].1.contains("move_down"), "{:?}", errors);
+        assert!(Keymap::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn help_line_follows_keymap() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("delete".to_string(), vec!["X".to_string()]);
+        let (keymap, errors) = Keymap::from_overrides(&overrides);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let help = keymap.help_line(&[Action::Delete, Action::Quit], Lang::Zh);
+        assert_eq!(help, " X 删除 · q 退出 ");
+        let help = keymap.help_line(&[Action::Delete, Action::Quit], Lang::En);
+        assert_eq!(help, " X delete · q quit ");
+    }
+
+    #[test]
+    fn help_sections_cover_every_action() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("help".to_string(), vec!["f1".to_string()]);
+        let (keymap, _) = Keymap::from_overrides(&overrides);
+        let sections = keymap.help_sections(Lang::En);
+        let total: usize = sections.iter().map(|(_, entries)| entries.len()).sum();
+        assert_eq!(total, Action::ALL.len());
+        let (category, general) = sections.last().unwrap();
+        assert_eq!(*category, Category::General);
+        assert!(general.contains(&("F1".to_string(), "Show this help")));
+    }
+}
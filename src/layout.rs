@@ -0,0 +1,262 @@
+use crate::i18n::Lang;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// 表格的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    /// 启用 / 禁用 / 失效
+    State,
+    /// 来源类型
+    Source,
+    /// 用户 / 系统
+    Scope,
+    Name,
+    /// 启动的可执行文件（未知时为定义文件路径）
+    Target,
+    /// 定义文件（或注册表键）的修改时间
+    Modified,
+    /// 正在运行的进程 PID
+    Process,
+    /// launchd 任务的运行时机
+    Trigger,
+}
+
+impl Column {
+    pub const ALL: &'static [Column] = &[
+        Column::State,
+        Column::Source,
+        Column::Scope,
+        Column::Name,
+        Column::Target,
+        Column::Modified,
+        Column::Process,
+        Column::Trigger,
+    ];
+
+    pub fn title(self, lang: Lang) -> &'static str {
+        match self {
+            Column::State => lang.pick("状态", "State"),
+            Column::Source => lang.pick("来源", "Source"),
+            Column::Scope => lang.pick("范围", "Scope"),
+            Column::Name => lang.pick("名称", "Name"),
+            Column::Target => lang.pick("目标", "Target"),
+            Column::Modified => lang.pick("修改时间 (UTC)", "Modified (UTC)"),
+            Column::Process => lang.pick("进程", "Process"),
+            Column::Trigger => lang.pick("触发", "Trigger"),
+        }
+    }
+
+    fn default_width(self) -> u16 {
+        match self {
+            Column::State => 8,
+            Column::Source => 14,
+            Column::Scope => 6,
+            Column::Name => 32,
+            Column::Target => 40,
+            Column::Modified => 16,
+            Column::Process => 12,
+            Column::Trigger => 14,
+        }
+    }
+}
+
+/// 单列的布局
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub column: Column,
+    pub width: u16,
+    pub visible: bool,
+}
+
+/// 列表布局：两行列表或表格，以及表格各列的显示与宽度。
+/// 保存在用户数据目录下的 `bootwatch/layout.toml`，修改后立即写入，下次启动恢复。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewLayout {
+    /// 使用表格而非两行列表
+    pub table: bool,
+    pub columns: Vec<ColumnLayout>,
+}
+
+impl Default for ViewLayout {
+    fn default() -> Self {
+        ViewLayout {
+            table: false,
+            columns: Column::ALL
+                .iter()
+                .map(|&column| ColumnLayout {
+                    column,
+                    width: column.default_width(),
+                    visible: true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ViewLayout {
+    pub const MIN_WIDTH: u16 = 4;
+    pub const MAX_WIDTH: u16 = 120;
+
+    /// 布局文件路径
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("bootwatch").join("layout.toml"))
+    }
+
+    /// 读取保存的布局；文件不存在或无法解析时使用默认布局
+    pub fn load() -> ViewLayout {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn parse(text: &str) -> ViewLayout {
+        toml::from_str::<ViewLayout>(text)
+            .map(ViewLayout::normalized)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| io::Error::other("无法确定用户数据目录"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// 去掉重复的列、补上缺失的列（追加在末尾）、修正越界的宽度，并保证至少一列可见
+    fn normalized(mut self) -> ViewLayout {
+        let mut seen = vec![];
+        self.columns.retain(|c| {
+            let first = !seen.contains(&c.column);
+            seen.push(c.column);
+            first
+        });
+        for &column in Column::ALL {
+            if !seen.contains(&column) {
+                self.columns.push(ColumnLayout {
+                    column,
+                    width: column.default_width(),
+                    visible: true,
+                });
+            }
+        }
+        for c in &mut self.columns {
+            c.width = c.width.clamp(Self::MIN_WIDTH, Self::MAX_WIDTH);
+        }
+        if !self.columns.iter().any(|c| c.visible) {
+            self.columns[0].visible = true;
+        }
+        self
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &ColumnLayout> {
+        self.columns.iter().filter(|c| c.visible)
+    }
+
+    /// 调整第 `index` 列的宽度
+    pub fn resize(&mut self, index: usize, delta: i16) {
+        if let Some(c) = self.columns.get_mut(index) {
+            c.width = c
+                .width
+                .saturating_add_signed(delta)
+                .clamp(Self::MIN_WIDTH, Self::MAX_WIDTH);
+        }
+    }
+
+    /// 显示 / 隐藏第 `index` 列；最后一列可见列不能隐藏，返回是否已切换
+    pub fn toggle(&mut self, index: usize) -> bool {
+        let visible = self.visible().count();
+        match self.columns.get_mut(index) {
+            Some(c) if !(c.visible && visible == 1) => {
+                c.visible = !c.visible;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 把第 `index` 列与相邻列交换位置，返回新位置
+    pub fn shift(&mut self, index: usize, forward: bool) -> usize {
+        let other = if forward {
+            index + 1
+        } else {
+            index.wrapping_sub(1)
+        };
+        if index < self.columns.len() && other < self.columns.len() {
+            self.columns.swap(index, other);
+            other
+        } else {
+            index
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut layout = ViewLayout {
+            table: true,
+            ..ViewLayout::default()
+        };
+        layout.resize(3, 8);
+        layout.toggle(1);
+        let text = toml::to_string(&layout).unwrap();
+        assert_eq!(ViewLayout::parse(&text), layout);
+    }
+
+    #[test]
+    fn parse_fills_missing_and_drops_duplicates() {
+        let layout = ViewLayout::parse(
+            r#"
+            table = true
+            [[columns]]
+            column = "name"
+            width = 500
+            visible = true
+            [[columns]]
+            column = "name"
+            width = 10
+            visible = false
+            "#,
+        );
+        assert!(layout.table);
+        assert_eq!(layout.columns.len(), Column::ALL.len());
+        assert_eq!(layout.columns[0].column, Column::Name);
+        assert_eq!(layout.columns[0].width, ViewLayout::MAX_WIDTH);
+    }
+
+    #[test]
+    fn invalid_file_falls_back_to_default() {
+        assert_eq!(ViewLayout::parse("columns = 3"), ViewLayout::default());
+    }
+
+    #[test]
+    fn last_visible_column_stays() {
+        let mut layout = ViewLayout::default();
+        for i in 0..layout.columns.len() {
+            layout.toggle(i);
+        }
+        assert_eq!(layout.visible().count(), 1);
+        assert!(layout.columns.last().unwrap().visible);
+    }
+
+    #[test]
+    fn resize_and_shift_clamp() {
+        let mut layout = ViewLayout::default();
+        layout.resize(0, -100);
+        assert_eq!(layout.columns[0].width, ViewLayout::MIN_WIDTH);
+        assert_eq!(layout.shift(0, false), 0);
+        assert_eq!(layout.shift(0, true), 1);
+        assert_eq!(layout.columns[1].column, Column::State);
+    }
+}
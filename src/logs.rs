@@ -0,0 +1,276 @@
+use crate::view::Filter;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// 打开时每个文件最多读入末尾的字节数
+const INITIAL_BYTES: u64 = 64 * 1024;
+/// 保留的最大行数，超出后丢弃最早的行
+const MAX_LINES: usize = 10_000;
+
+/// 跟随（tail）一个日志文件：记录已读位置，每次轮询读入新增内容；
+/// 文件变短（被截断或轮转替换）时从头重读
+struct Tail {
+    path: PathBuf,
+    offset: u64,
+    /// 尚未以换行结尾的最后一行
+    partial: String,
+    /// 从文件中间开始读时，丢弃第一行残缺的内容
+    skip_first: bool,
+    error: Option<String>,
+}
+
+impl Tail {
+    fn open(path: PathBuf) -> Tail {
+        let len = path.metadata().map(|m| m.len()).unwrap_or(0);
+        let offset = len.saturating_sub(INITIAL_BYTES);
+        Tail {
+            path,
+            offset,
+            partial: String::new(),
+            skip_first: offset > 0,
+            error: None,
+        }
+    }
+
+    /// 读入新增的完整行
+    fn read_lines(&mut self) -> io::Result<Vec<String>> {
+        let mut file = File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+            self.skip_first = false;
+        }
+        if len == self.offset {
+            return Ok(vec![]);
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = vec![];
+        file.take(len - self.offset).read_to_end(&mut buf)?;
+        self.offset += buf.len() as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&buf));
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(vec![]);
+        };
+        let rest = self.partial.split_off(end + 1);
+        let mut lines: Vec<String> = self
+            .partial
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect();
+        self.partial = rest;
+        if std::mem::take(&mut self.skip_first) && !lines.is_empty() {
+            lines.remove(0);
+        }
+        Ok(lines)
+    }
+}
+
+/// 日志查看器：跟随一个或多个日志文件，新行按到达顺序追加，可搜索
+pub struct LogView {
+    pub title: String,
+    files: Vec<Tail>,
+    /// (文件序号, 行内容)
+    lines: VecDeque<(usize, String)>,
+    /// 顶部行号；`None` 表示跟随末尾
+    pub scroll: Option<usize>,
+    pub filter: Filter,
+    /// 正在输入搜索关键字
+    pub searching: bool,
+    /// 上次绘制时的可见行数，用于滚动
+    pub height: usize,
+}
+
+impl LogView {
+    pub fn open(title: String, paths: Vec<PathBuf>) -> LogView {
+        let mut view = LogView {
+            title,
+            files: paths.into_iter().map(Tail::open).collect(),
+            lines: VecDeque::new(),
+            scroll: None,
+            filter: Filter::default(),
+            searching: false,
+            height: 1,
+        };
+        view.poll();
+        view
+    }
+
+    /// 读入各文件的新增内容，返回是否有新行
+    pub fn poll(&mut self) -> bool {
+        let mut added = false;
+        for (i, tail) in self.files.iter_mut().enumerate() {
+            match tail.read_lines() {
+                Ok(lines) => {
+                    tail.error = None;
+                    added |= !lines.is_empty();
+                    self.lines.extend(lines.into_iter().map(|line| (i, line)));
+                }
+                Err(e) => tail.error = Some(e.to_string()),
+            }
+        }
+        let excess = self.lines.len().saturating_sub(MAX_LINES);
+        if excess > 0 {
+            self.lines.drain(..excess);
+            if let Some(top) = &mut self.scroll {
+                *top = top.saturating_sub(excess);
+            }
+        }
+        added
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&Path, Option<&str>)> {
+        self.files
+            .iter()
+            .map(|t| (t.path.as_path(), t.error.as_deref()))
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn lines(&self) -> &VecDeque<(usize, String)> {
+        &self.lines
+    }
+
+    /// 当前可见的第一行
+    pub fn top(&self) -> usize {
+        let max = self.lines.len().saturating_sub(self.height);
+        self.scroll.map_or(max, |top| top.min(max))
+    }
+
+    /// 向上（负）或向下滚动；滚到末尾时恢复跟随
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(self.height);
+        let top = self.top().saturating_add_signed(delta).min(max);
+        self.scroll = (top < max).then_some(top);
+    }
+
+    /// 从当前位置起向下（或向上）查找下一处匹配，并滚动到该行；没有匹配时返回 `false`
+    pub fn jump_match(&mut self, forward: bool) -> bool {
+        if self.filter.is_empty() {
+            return false;
+        }
+        let len = self.lines.len();
+        let top = self.top();
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (top + step) % len
+                } else {
+                    (top + len - step % len) % len
+                }
+            })
+            .find(|&i| self.filter.find(&self.lines[i].1).is_some());
+        match found {
+            Some(i) => {
+                self.scroll = Some(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 匹配搜索关键字的行数
+    pub fn match_count(&self) -> usize {
+        if self.filter.is_empty() {
+            return 0;
+        }
+        self.lines
+            .iter()
+            .filter(|(_, line)| self.filter.find(line).is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bootwatch-logs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn texts(view: &LogView) -> Vec<&str> {
+        view.lines().iter().map(|(_, l)| l.as_str()).collect()
+    }
+
+    #[test]
+    fn follows_appended_lines() {
+        let path = temp_log("follow.log");
+        append(&path, "one\ntw");
+        let mut view = LogView::open("t".to_string(), vec![path.clone()]);
+        assert_eq!(texts(&view), ["one"]);
+        append(&path, "o\nthree\n");
+        assert!(view.poll());
+        assert_eq!(texts(&view), ["one", "two", "three"]);
+        assert!(!view.poll());
+    }
+
+    #[test]
+    fn rereads_truncated_file() {
+        let path = temp_log("truncate.log");
+        append(&path, "old line\nanother\n");
+        let mut view = LogView::open("t".to_string(), vec![path.clone()]);
+        fs::write(&path, "new\n").unwrap();
+        view.poll();
+        assert_eq!(texts(&view), ["old line", "another", "new"]);
+    }
+
+    #[test]
+    fn large_file_starts_at_line_boundary() {
+        let path = temp_log("large.log");
+        let line = "x".repeat(99);
+        let text = format!("{}\n", line).repeat(2_000);
+        append(&path, &text);
+        let view = LogView::open("t".to_string(), vec![path]);
+        assert!(view.lines().len() < 2_000);
+        assert!(texts(&view).iter().all(|l| *l == line));
+    }
+
+    #[test]
+    fn missing_file_reports_error() {
+        let path = temp_log("missing.log");
+        let view = LogView::open("t".to_string(), vec![path]);
+        assert!(view.files().all(|(_, error)| error.is_some()));
+    }
+
+    #[test]
+    fn scroll_and_search() {
+        let path = temp_log("search.log");
+        append(&path, "a\nerror 1\nb\nc\nerror 2\nd\n");
+        let mut view = LogView::open("t".to_string(), vec![path]);
+        view.height = 2;
+        assert_eq!(view.top(), 4);
+        view.scroll_by(-3);
+        assert_eq!(view.scroll, Some(1));
+        view.scroll_by(10);
+        assert_eq!(view.scroll, None);
+
+        view.filter.set_query("ERROR".to_string());
+        assert_eq!(view.match_count(), 2);
+        assert!(view.jump_match(true));
+        assert_eq!(view.scroll, Some(1));
+        assert!(view.jump_match(true));
+        assert_eq!(view.scroll, Some(4));
+        assert!(view.jump_match(false));
+        assert_eq!(view.scroll, Some(1));
+    }
+}
@@ -1,7 +1,85 @@
+#[macro_use]
+mod i18n;
+mod config;
+mod keymap;
+mod layout;
+mod logs;
 mod platform;
+mod scan;
+mod theme;
 mod tui;
+mod view;
+
+use std::path::PathBuf;
+
+const USAGE: &str = "用法: bootwatch [--offline <Windows 卷挂载点>]
+
+  --offline <目录>  只读地扫描挂载的 Windows 卷中的注册表 hive 与计划任务
+                    （Windows\\System32\\config\\SOFTWARE 与 Users\\*\\NTUSER.DAT）
+  -h, --help        显示本帮助";
+
+/// 命令行参数
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Args {
+    offline: Option<PathBuf>,
+    help: bool,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--offline" => {
+                let root = args.next().ok_or("--offline 需要指定目录")?;
+                parsed.offline = Some(PathBuf::from(shellexpand::tilde(&root).into_owned()));
+            }
+            "-h" | "--help" => parsed.help = true,
+            _ => return Err(format!("未知参数: {}", arg)),
+        }
+    }
+    Ok(parsed)
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tui::run()?;
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if let Some(root) = &args.offline {
+        if !root.is_dir() {
+            eprintln!("不是目录: {}", root.display());
+            std::process::exit(2);
+        }
+    }
+    tui::run(args.offline)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_offline_root() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+        assert_eq!(
+            parse(&["--offline", "/mnt/win"]).unwrap().offline,
+            Some(PathBuf::from("/mnt/win"))
+        );
+        assert!(parse(&["--offline"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["-h"]).unwrap().help);
+    }
+}
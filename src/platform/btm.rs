@@ -0,0 +1,234 @@
+use crate::platform::plist::{self, Value};
+
+/// Background Task Management 条目的类型（`type` 字段的位掩码）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    App,
+    LoginItem,
+    Agent,
+    Daemon,
+    /// 开发者分组，其下挂各条目
+    Developer,
+    Other(i64),
+}
+
+impl Kind {
+    fn from_type(bits: i64) -> Kind {
+        if bits & 0x10 != 0 {
+            Kind::Daemon
+        } else if bits & 0x8 != 0 {
+            Kind::Agent
+        } else if bits & 0x4 != 0 {
+            Kind::LoginItem
+        } else if bits & 0x2 != 0 {
+            Kind::App
+        } else if bits & 0x20 != 0 {
+            Kind::Developer
+        } else {
+            Kind::Other(bits)
+        }
+    }
+}
+
+/// `disposition` 中表示已启用的位（系统设置中的开关）
+const DISPOSITION_ENABLED: i64 = 0x1;
+/// `type` 中表示旧式（非 SMAppService 注册）条目的位
+const TYPE_LEGACY: i64 = 0x10000;
+
+/// BTM 数据库中的一个条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub name: String,
+    pub developer: Option<String>,
+    pub bundle_id: Option<String>,
+    /// 如 `16.com.example.agent`
+    pub identifier: Option<String>,
+    pub executable: Option<String>,
+    /// `url` 指向的本地路径：agent / daemon 为 plist，app 为 `.app` 目录
+    pub path: Option<String>,
+    pub kind: Kind,
+    pub legacy: bool,
+    pub enabled: bool,
+}
+
+/// 解析 `BackgroundItems-v*.btm`：NSKeyedArchiver 归档的二进制 plist，
+/// 根对象的 `itemsByUserIdentifier` 为 用户 UUID → 条目数组
+pub fn parse(bytes: &[u8]) -> Result<Vec<Item>, String> {
+    const ERR: &str = "BTM 数据库格式无法识别";
+    let plist = plist::parse(bytes)?;
+    let objects = plist
+        .get("$objects")
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("{}: 缺少 $objects", ERR))?;
+    let archive = Archive { objects };
+    // 根对象在 `$top` 中通常名为 `store`
+    let top = match plist.get("$top") {
+        Some(Value::Dict(top)) => top.get("store").or_else(|| top.values().next()),
+        _ => None,
+    };
+    let root = top
+        .and_then(|uid| archive.resolve(uid))
+        .ok_or_else(|| format!("{}: 缺少根对象", ERR))?;
+    let users = archive
+        .get(root, "itemsByUserIdentifier")
+        .ok_or_else(|| format!("{}: 缺少 itemsByUserIdentifier", ERR))?;
+    Ok(archive
+        .array(users)
+        .into_iter()
+        .flat_map(|records| archive.array(records))
+        .filter_map(|record| archive.item(record))
+        .collect())
+}
+
+/// NSKeyedArchiver 归档：对象之间以 UID 引用 `$objects` 中的下标
+struct Archive<'a> {
+    objects: &'a [Value],
+}
+
+impl<'a> Archive<'a> {
+    /// 解开 UID 引用；`$null` 视为不存在
+    fn resolve(&self, value: &'a Value) -> Option<&'a Value> {
+        let value = match value {
+            Value::Uid(i) => self.objects.get(*i as usize)?,
+            other => other,
+        };
+        (value.as_str() != Some("$null")).then_some(value)
+    }
+
+    fn get(&self, object: &'a Value, key: &str) -> Option<&'a Value> {
+        self.resolve(object.get(key)?)
+    }
+
+    fn string(&self, object: &'a Value, key: &str) -> Option<String> {
+        let value = self.get(object, key)?;
+        let text = match value.as_str() {
+            Some(s) => s.to_string(),
+            // NSURL：`NS.base` 非空时与 `NS.relative` 拼接
+            None => {
+                let relative = self.get(value, "NS.relative")?.as_str()?;
+                match self.get(value, "NS.base").and_then(Value::as_str) {
+                    Some(base) => format!("{}{}", base, relative),
+                    None => relative.to_string(),
+                }
+            }
+        };
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// NSArray / NSSet 的元素；用于 NSDictionary 时为其值（与 `NS.keys` 一一对应）
+    fn array(&self, object: &'a Value) -> Vec<&'a Value> {
+        self.get(object, "NS.objects")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| self.resolve(v))
+            .collect()
+    }
+
+    fn item(&self, record: &'a Value) -> Option<Item> {
+        let bits = match record.get("type") {
+            Some(Value::Integer(bits)) => *bits,
+            _ => return None,
+        };
+        let disposition = match record.get("disposition") {
+            Some(Value::Integer(d)) => *d,
+            _ => 0,
+        };
+        Some(Item {
+            name: self.string(record, "name")?,
+            developer: self.string(record, "developerName"),
+            bundle_id: self.string(record, "bundleIdentifier"),
+            identifier: self.string(record, "identifier"),
+            executable: self.string(record, "executablePath"),
+            path: self.string(record, "url").and_then(|url| url_to_path(&url)),
+            kind: Kind::from_type(bits),
+            legacy: bits & TYPE_LEGACY != 0,
+            enabled: disposition & DISPOSITION_ENABLED != 0,
+        })
+    }
+}
+
+/// `file://` URL 转为本地路径（解码 `%xx`，去掉目录末尾的 `/`）；其它 scheme 返回 `None`
+pub fn url_to_path(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("file://")?;
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let mut bytes = vec![];
+    let mut iter = rest.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex: Vec<u8> = iter.by_ref().take(2).collect();
+            let decoded = std::str::from_utf8(&hex)
+                .ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok())?;
+            bytes.push(decoded);
+        } else {
+            bytes.push(b);
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    let trimmed = path.trim_end_matches('/');
+    Some(if trimmed.is_empty() {
+        path
+    } else {
+        trimmed.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/BackgroundItems-v4.btm");
+
+    #[test]
+    fn parses_fixture() {
+        let items = parse(FIXTURE).unwrap();
+        let kinds: Vec<Kind> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            [Kind::Developer, Kind::Agent, Kind::LoginItem, Kind::Daemon]
+        );
+
+        let agent = &items[1];
+        assert_eq!(agent.name, "com.example.agent");
+        assert_eq!(agent.developer.as_deref(), Some("Example Corp"));
+        assert_eq!(agent.bundle_id.as_deref(), Some("com.example.app"));
+        assert_eq!(
+            agent.path.as_deref(),
+            Some("/Library/LaunchAgents/com.example.agent.plist")
+        );
+        assert_eq!(
+            agent.executable.as_deref(),
+            Some("/Applications/Example.app/Contents/MacOS/agent")
+        );
+        assert!(agent.enabled && !agent.legacy);
+
+        let helper = &items[2];
+        assert_eq!(
+            helper.path.as_deref(),
+            Some("/Applications/Example Helper.app")
+        );
+        assert_eq!(helper.executable, None);
+        assert!(!helper.enabled);
+
+        let daemon = &items[3];
+        assert_eq!(daemon.developer, None);
+        assert!(daemon.legacy);
+    }
+
+    #[test]
+    fn rejects_other_archives() {
+        assert!(parse(include_bytes!("../../tests/fixtures/agent.bplist")).is_err());
+        assert!(parse(b"not a plist").is_err());
+    }
+
+    #[test]
+    fn file_urls() {
+        assert_eq!(
+            url_to_path("file:///Applications/A%20B.app/").as_deref(),
+            Some("/Applications/A B.app")
+        );
+        assert_eq!(url_to_path("file:///").as_deref(), Some("/"));
+        assert_eq!(url_to_path("https://example.com"), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 外部命令的执行器：超时或收到取消信号时结束子进程，避免卡住调用方
+/// （如 `osascript` 等待自动化权限弹窗时）。
+#[derive(Debug, Clone)]
+pub struct Runner {
+    timeout: Duration,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Runner {
+    pub fn new(timeout: Duration) -> Runner {
+        Runner::with_cancel(timeout, Arc::new(AtomicBool::new(false)))
+    }
+
+    /// 共享取消标志的执行器，标志置位后正在运行的命令会被结束
+    pub fn with_cancel(timeout: Duration, cancel: Arc<AtomicBool>) -> Runner {
+        Runner { timeout, cancel }
+    }
+
+    /// 执行命令并收集输出。超时返回 `TimedOut`，取消返回 `Interrupted`
+    pub fn output(&self, command: &mut Command) -> io::Result<Output> {
+        let program = command.get_program().to_string_lossy().into_owned();
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // 在独立线程中读取输出，避免管道写满导致子进程阻塞
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            let error = if self.cancel.load(Ordering::Relaxed) {
+                io::Error::new(io::ErrorKind::Interrupted, format!("已取消: {}", program))
+            } else if Instant::now() >= deadline {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("命令超时（{} 秒）: {}", self.timeout.as_secs(), program),
+                )
+            } else {
+                thread::sleep(Duration::from_millis(20));
+                continue;
+            };
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error);
+        };
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = vec![];
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_output() {
+        let runner = Runner::new(Duration::from_secs(5));
+        let output = runner
+            .output(Command::new("sh").args(["-c", "echo out; echo err >&2"]))
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[test]
+    fn kills_on_timeout() {
+        let runner = Runner::new(Duration::from_millis(100));
+        let started = Instant::now();
+        let err = runner.output(Command::new("sleep").arg("5")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn kills_on_cancel() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let runner = Runner::with_cancel(Duration::from_secs(5), cancel.clone());
+        let handle = thread::spawn(move || runner.output(Command::new("sleep").arg("5")));
+        thread::sleep(Duration::from_millis(50));
+        cancel.store(true, Ordering::Relaxed);
+        let err = handle.join().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+}
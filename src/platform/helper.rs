@@ -1,3 +1,8 @@
+use crate::i18n::Lang;
+use crate::platform::process::ProcessInfo;
+use std::path::Path;
+use std::time::SystemTime;
+
 /// 删除令牌的统一解析：返回 `(kind, payload)`。
 ///
 /// 令牌格式为 `<kind>|<payload>`，其中 payload 可能自身包含 `|`
@@ -13,19 +18,348 @@ pub struct OptionItem {
     pub value: String,
 }
 
+/// 启动项的作用范围：仅当前用户，或全系统
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    User,
+    System,
+}
+
+impl Scope {
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            Scope::User => lang.pick("用户", "user"),
+            Scope::System => lang.pick("系统", "system"),
+        }
+    }
+}
+
 /// 用于 TUI 展示的启动项信息，内嵌用于删除的 OptionItem
 #[derive(Debug, Clone)]
 pub struct DisplayItem {
-    pub icon: String,
+    /// 来源 ID（见 `platform::SOURCES`），也决定图标
+    pub source: &'static str,
     pub type_label: String,
     pub label: String,
     pub path: Option<String>,
+    /// 启动时执行的命令行（已知时）
+    pub command: Option<String>,
+    pub scope: Scope,
+    /// 定义所在位置：目录或注册表键，用于分组
+    pub location: Option<String>,
+    /// 定义文件（或注册表键）的最后修改时间
+    pub modified: Option<SystemTime>,
+    /// 命令指向的可执行文件，不存在时视为失效项
+    pub target: Option<String>,
+    pub broken: bool,
+    /// 已被禁用（定义仍在，但不会开机运行）
+    pub disabled: bool,
+    /// 受系统保护（如 macOS SIP），不能删除、禁用或编辑
+    pub protected: bool,
+    /// 开发者（已知时）
+    pub developer: Option<String>,
+    /// 登录时隐藏窗口（macOS 登录项）
+    pub hidden: bool,
+    /// 打开的对象种类（macOS 登录项，如「应用程序」）；
+    /// Windows 注册表项为所在自启动键的简称（如 `RunOnce`）
+    pub kind: Option<String>,
+    /// 系统自带的启动项管理界面（Windows 任务管理器「启动」页）不显示该项
+    pub unlisted: bool,
+    /// 运行身份（Windows 计划任务的 Principal，如 `SYSTEM`）
+    pub principal: Option<String>,
+    /// 正在运行的实例（按可执行文件与参数匹配），随进程快照更新
+    pub running: Option<ProcessInfo>,
+    /// launchd 任务与计划任务的运行时机；为空表示其它启动项（开机或登录时启动）
+    pub triggers: Vec<Trigger>,
     pub option: OptionItem,
 }
 
+impl DisplayItem {
+    /// 是否在开机或登录时启动
+    pub fn runs_at_startup(&self) -> bool {
+        self.triggers.is_empty() || self.triggers.iter().any(|t| t.at_startup())
+    }
+
+    /// 运行时机的摘要，如 `加载时, 每 3600 秒`；非 launchd 任务或计划任务为 `None`
+    pub fn trigger_summary(&self, lang: Lang) -> Option<String> {
+        (!self.triggers.is_empty()).then(|| {
+            self.triggers
+                .iter()
+                .map(|t| t.label(lang))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+}
+
+/// launchd 任务（由 plist 中的键推断）或 Windows 计划任务（由触发器推断）的运行时机
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// `RunAtLoad`：加载时（开机或登录）运行一次
+    AtLoad,
+    /// `KeepAlive`：加载时启动并保持运行
+    KeepAlive,
+    /// `StartInterval`：每隔若干秒运行
+    Interval(u64),
+    /// `StartCalendarInterval`：按日历时间运行
+    Calendar,
+    /// `WatchPaths` / `QueueDirectories`：路径变化时运行
+    WatchPaths,
+    /// 只在被请求时运行（`MachServices`、`Sockets` 等，或未声明任何触发条件）
+    OnDemand,
+    /// 计划任务的 `BootTrigger`：开机时运行
+    Boot,
+    /// 计划任务的 `LogonTrigger`：用户登录时运行
+    Logon,
+}
+
+impl Trigger {
+    pub fn label(self, lang: Lang) -> String {
+        match self {
+            Trigger::AtLoad => lang.pick("加载时", "at load").to_string(),
+            Trigger::KeepAlive => lang.pick("常驻", "keep-alive").to_string(),
+            Trigger::Interval(secs) => tr!(lang, "每 {} 秒", "every {}s", secs),
+            Trigger::Calendar => lang.pick("定时", "calendar").to_string(),
+            Trigger::WatchPaths => lang.pick("路径变化时", "on path change").to_string(),
+            Trigger::OnDemand => lang.pick("按需", "on demand").to_string(),
+            Trigger::Boot => lang.pick("开机时", "at boot").to_string(),
+            Trigger::Logon => lang.pick("登录时", "at logon").to_string(),
+        }
+    }
+
+    /// 是否在开机或登录时启动
+    pub fn at_startup(self) -> bool {
+        matches!(
+            self,
+            Trigger::AtLoad | Trigger::KeepAlive | Trigger::Boot | Trigger::Logon
+        )
+    }
+}
+
+/// 从命令行中取出可执行文件路径：
+/// 以引号开头时取引号内内容；否则取到 `.exe` 为止（路径中可含空格），再否则取第一个空白前的部分
+pub fn executable_of(command: &str) -> Option<String> {
+    let command = command.trim();
+    if let Some(rest) = command.strip_prefix('"') {
+        return rest
+            .split('"')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+    }
+    let lower = command.to_ascii_lowercase();
+    if let Some(pos) = lower.find(".exe") {
+        return Some(command[..pos + 4].to_string());
+    }
+    command.split_whitespace().next().map(str::to_string)
+}
+
+/// 命令行中可执行文件之后的参数，拆分规则与 `executable_of` 一致
+pub fn arguments_of(command: &str) -> Vec<String> {
+    let command = command.trim();
+    let rest = if let Some(rest) = command.strip_prefix('"') {
+        rest.split_once('"').map_or("", |(_, rest)| rest)
+    } else if let Some(pos) = command.to_ascii_lowercase().find(".exe") {
+        &command[pos + 4..]
+    } else {
+        command
+            .split_once(char::is_whitespace)
+            .map_or("", |(_, rest)| rest)
+    };
+    split_command_line(rest)
+}
+
+/// 展开 Windows 风格的 `%VAR%` 环境变量，未定义的变量保持原样
+pub fn expand_env_vars(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(end) if end > 0 => {
+                let name = &after[..end];
+                match std::env::var(name) {
+                    Ok(value) => out.push_str(&value),
+                    Err(_) => out.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('%');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 可执行文件是否缺失：只对绝对路径判断，裸命令名（依赖 PATH 查找）不视为失效
+pub fn is_missing(target: &str) -> bool {
+    let path = Path::new(target);
+    path.is_absolute() && !path.exists()
+}
+
+/// 文件的最后修改时间
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+/// 格式化为 `YYYY-MM-DD HH:MM`（UTC）
+pub fn format_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // 由 1970-01-01 起的天数推算公历日期（Howard Hinnant 的 civil_from_days）
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60
+    )
+}
+
+/// 按空白拆分命令行，双引号内的空白不拆分（用于 `$VISUAL` / `$EDITOR`，如 `code --wait`）
+pub fn split_command_line(command: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in command.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    args.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            c => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        args.push(current);
+    }
+    args
+}
+
+/// Windows FILETIME（自 1601-01-01 起的 100 纳秒数）转为 `SystemTime`
+pub fn filetime_to_system_time(ticks: u64) -> Option<SystemTime> {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    // 1601-01-01 到 1970-01-01 之间的 100 纳秒数
+    const EPOCH_DIFF: u64 = 116_444_736_000_000_000;
+    let since_unix = ticks.checked_sub(EPOCH_DIFF)?;
+    Some(UNIX_EPOCH + Duration::from_nanos(since_unix.checked_mul(100)?))
+}
+
+/// Windows 注册表中的一个自启动键，`HKCU` 与 `HKLM` 下位置相同
+pub struct RunKey {
+    /// 相对 hive 中 `Software` 键的路径
+    pub path: &'static str,
+    /// 展示用的简称
+    pub name: &'static str,
+    /// 任务管理器记录启用状态的 `StartupApproved` 子键；
+    /// 为 `None` 时任务管理器「启动」页不显示该键中的启动项，也无法禁用
+    pub approved: Option<&'static str>,
+    /// 命令不在该键本身，而在其各个子键中（`RunOnceEx`）
+    pub nested: bool,
+}
+
+/// 扫描的全部自启动键，按任务管理器可见的在前排列
+pub const RUN_KEYS: &[RunKey] = &[
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\Run",
+        name: "Run",
+        approved: Some("Run"),
+        nested: false,
+    },
+    RunKey {
+        path: r"WOW6432Node\Microsoft\Windows\CurrentVersion\Run",
+        name: r"Wow6432Node\Run",
+        approved: Some("Run32"),
+        nested: false,
+    },
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\RunOnce",
+        name: "RunOnce",
+        approved: None,
+        nested: false,
+    },
+    RunKey {
+        path: r"WOW6432Node\Microsoft\Windows\CurrentVersion\RunOnce",
+        name: r"Wow6432Node\RunOnce",
+        approved: None,
+        nested: false,
+    },
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\RunOnceEx",
+        name: "RunOnceEx",
+        approved: None,
+        nested: true,
+    },
+    RunKey {
+        path: r"WOW6432Node\Microsoft\Windows\CurrentVersion\RunOnceEx",
+        name: r"Wow6432Node\RunOnceEx",
+        approved: None,
+        nested: true,
+    },
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\Policies\Explorer\Run",
+        name: r"Policies\Explorer\Run",
+        approved: None,
+        nested: false,
+    },
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\RunServices",
+        name: "RunServices",
+        approved: None,
+        nested: false,
+    },
+    RunKey {
+        path: r"Microsoft\Windows\CurrentVersion\RunServicesOnce",
+        name: "RunServicesOnce",
+        approved: None,
+        nested: false,
+    },
+];
+
+/// 完整键路径（如 `HKEY_CURRENT_USER\Software\Microsoft\...\Run`）所属的自启动键；
+/// `RunOnceEx` 的子键同样归属于 `RunOnceEx`
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn run_key_of(full_key: &str) -> Option<&'static RunKey> {
+    let lower = full_key.to_ascii_lowercase();
+    let (_, rest) = lower.split_once(r"\software\")?;
+    RUN_KEYS.iter().find(|key| {
+        let path = key.path.to_ascii_lowercase();
+        rest == path || (key.nested && rest.starts_with(&format!(r"{}\", path)))
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_token;
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn parse_token_kind_and_payload() {
@@ -62,4 +396,140 @@ mod tests {
         assert_eq!(kind, "");
         assert_eq!(payload, "");
     }
+
+    #[test]
+    fn split_command_line_keeps_quoted_args() {
+        assert_eq!(split_command_line("code  --wait"), ["code", "--wait"]);
+        assert_eq!(
+            split_command_line(r#""C:\Program Files\Vim\vim.exe" -p"#),
+            [r"C:\Program Files\Vim\vim.exe", "-p"]
+        );
+        assert!(split_command_line("   ").is_empty());
+    }
+
+    #[test]
+    fn executable_of_quoted_path() {
+        assert_eq!(
+            executable_of(r#""C:\Program Files\App\app.exe" --tray"#).as_deref(),
+            Some(r"C:\Program Files\App\app.exe")
+        );
+    }
+
+    #[test]
+    fn executable_of_unquoted_exe_with_spaces() {
+        assert_eq!(
+            executable_of(r"C:\Program Files\App\app.exe /background").as_deref(),
+            Some(r"C:\Program Files\App\app.exe")
+        );
+    }
+
+    #[test]
+    fn executable_of_plain_command() {
+        assert_eq!(
+            executable_of("/usr/bin/env python3 agent.py").as_deref(),
+            Some("/usr/bin/env")
+        );
+        assert_eq!(executable_of("   "), None);
+    }
+
+    #[test]
+    fn arguments_follow_executable() {
+        assert_eq!(
+            arguments_of(r#""C:\Program Files\App\app.exe" --tray "a b""#),
+            ["--tray", "a b"]
+        );
+        assert_eq!(
+            arguments_of(r"C:\Program Files\App\app.exe /background"),
+            ["/background"]
+        );
+        assert!(arguments_of("/usr/bin/agent").is_empty());
+    }
+
+    #[test]
+    fn expand_env_vars_known_and_unknown() {
+        std::env::set_var("BOOTWATCH_TEST_DIR", "/opt/x");
+        assert_eq!(
+            expand_env_vars("%BOOTWATCH_TEST_DIR%\\a.exe"),
+            "/opt/x\\a.exe"
+        );
+        assert_eq!(
+            expand_env_vars("%BOOTWATCH_UNDEFINED_VAR%\\a"),
+            "%BOOTWATCH_UNDEFINED_VAR%\\a"
+        );
+        assert_eq!(expand_env_vars("100% sure"), "100% sure");
+    }
+
+    #[test]
+    fn relative_targets_are_not_missing() {
+        assert!(!is_missing("rundll32.exe"));
+        assert!(is_missing("/definitely/not/here/bootwatch-test"));
+    }
+
+    #[test]
+    fn format_time_utc() {
+        assert_eq!(format_time(UNIX_EPOCH), "1970-01-01 00:00");
+        assert_eq!(
+            format_time(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29 12:34"
+        );
+    }
+
+    #[test]
+    fn filetime_conversion() {
+        assert_eq!(
+            filetime_to_system_time(116_444_736_000_000_000),
+            Some(UNIX_EPOCH)
+        );
+        assert_eq!(
+            filetime_to_system_time(116_444_736_000_000_000 + 10_000_000),
+            Some(UNIX_EPOCH + Duration::from_secs(1))
+        );
+        assert_eq!(filetime_to_system_time(0), None);
+    }
+
+    #[test]
+    fn finds_run_key_of_path() {
+        let name = |path: &str| run_key_of(path).map(|key| key.name);
+        assert_eq!(
+            name(r"HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Run"),
+            Some("Run")
+        );
+        assert_eq!(
+            name(r"HKEY_LOCAL_MACHINE\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Run"),
+            Some(r"Wow6432Node\Run")
+        );
+        assert_eq!(
+            name(r"HKEY_LOCAL_MACHINE\Software\Microsoft\Windows\CurrentVersion\RunOnceEx\0001"),
+            Some("RunOnceEx")
+        );
+        // 只有 RunOnceEx 的子键归属于父键
+        assert_eq!(
+            name(r"HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Run\Sub"),
+            None
+        );
+        assert!(
+            run_key_of(r"HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\RunOnce")
+                .is_some_and(|key| key.approved.is_none())
+        );
+    }
+
+    #[test]
+    fn startup_triggers() {
+        let summary = |triggers: &[Trigger]| {
+            triggers
+                .iter()
+                .map(|t| t.label(Lang::En))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        assert_eq!(
+            summary(&[Trigger::AtLoad, Trigger::Interval(3600)]),
+            "at load, every 3600s"
+        );
+        assert!(Trigger::KeepAlive.at_startup());
+        assert!(Trigger::Boot.at_startup() && Trigger::Logon.at_startup());
+        assert!(![Trigger::Calendar, Trigger::WatchPaths, Trigger::OnDemand]
+            .iter()
+            .any(|t| t.at_startup()));
+    }
 }
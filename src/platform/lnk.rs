@@ -0,0 +1,331 @@
+use std::path::Path;
+
+/// 快捷方式中与启动相关的字段
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shortcut {
+    /// LinkInfo 中的目标：本地路径，或网络共享名加路径后缀
+    pub link_target: Option<String>,
+    /// 相对于快捷方式所在目录的目标路径
+    pub relative_path: Option<String>,
+    pub working_dir: Option<String>,
+    pub arguments: Option<String>,
+    /// 图标文件，可能含 `%VAR%`
+    pub icon_location: Option<String>,
+    /// EnvironmentVariableDataBlock 中含 `%VAR%` 的目标路径
+    pub env_target: Option<String>,
+}
+
+impl Shortcut {
+    /// 实际启动的目标：优先取含环境变量的路径（安装位置随系统变化时仍然正确），
+    /// 其次为 LinkInfo 中的路径；都没有时按快捷方式所在目录 `dir` 解析相对路径
+    pub fn target_in(&self, dir: &Path) -> Option<String> {
+        self.env_target
+            .clone()
+            .or_else(|| self.link_target.clone())
+            .or_else(|| Some(resolve_relative(dir, self.relative_path.as_deref()?)))
+    }
+
+    /// 目标与参数拼成的命令行，目标加引号，与 `helper::executable_of` 对应
+    pub fn command_in(&self, dir: &Path) -> Option<String> {
+        let target = self.target_in(dir)?;
+        Some(match &self.arguments {
+            Some(args) => format!("\"{}\" {}", target, args),
+            None => format!("\"{}\"", target),
+        })
+    }
+}
+
+/// 把相对路径接在 `dir` 之后并折叠 `.` 与 `..`，以 `\` 分隔；不会越过 `dir` 的第一段（盘符）
+fn resolve_relative(dir: &Path, relative: &str) -> String {
+    let dir = dir.to_string_lossy();
+    let mut parts: Vec<&str> = dir
+        .split(['\\', '/'])
+        .enumerate()
+        .filter(|(i, part)| *i == 0 || !part.is_empty())
+        .map(|(_, part)| part)
+        .collect();
+    for part in relative.split(['\\', '/']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.len() > 1 {
+                    parts.pop();
+                }
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("\\")
+}
+
+const HEADER_SIZE: u32 = 0x4C;
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+// LinkFlags
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x1;
+const HAS_LINK_INFO: u32 = 0x2;
+const HAS_NAME: u32 = 0x4;
+const HAS_RELATIVE_PATH: u32 = 0x8;
+const HAS_WORKING_DIR: u32 = 0x10;
+const HAS_ARGUMENTS: u32 = 0x20;
+const HAS_ICON_LOCATION: u32 = 0x40;
+const IS_UNICODE: u32 = 0x80;
+
+// LinkInfoFlags
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+const COMMON_NETWORK_RELATIVE_LINK: u32 = 0x2;
+
+const ENVIRONMENT_VARIABLE_BLOCK: u32 = 0xA000_0001;
+const ICON_ENVIRONMENT_BLOCK: u32 = 0xA000_0007;
+
+/// 解析 `.lnk` 文件。ANSI 字符串的代码页未知，按 UTF-8 宽松解码
+pub fn parse(bytes: &[u8]) -> Result<Shortcut, String> {
+    let r = Reader { bytes };
+    if r.u32(0) != Some(HEADER_SIZE) || bytes.get(4..20) != Some(&LINK_CLSID[..]) {
+        return Err("不是有效的快捷方式文件".to_string());
+    }
+    let truncated = || "快捷方式文件不完整".to_string();
+    let flags = r.u32(20).ok_or_else(truncated)?;
+    let mut pos = HEADER_SIZE as usize;
+    let mut shortcut = Shortcut::default();
+
+    // LinkTargetIDList 为 shell 命名空间路径，目标已在 LinkInfo 中给出，跳过
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        pos += 2 + r.u16(pos).ok_or_else(truncated)? as usize;
+    }
+    if flags & HAS_LINK_INFO != 0 {
+        let size = r.u32(pos).ok_or_else(truncated)? as usize;
+        let info = bytes.get(pos..pos + size).ok_or_else(truncated)?;
+        shortcut.link_target = link_info_target(info);
+        pos += size;
+    }
+
+    let unicode = flags & IS_UNICODE != 0;
+    let mut strings = [None, None, None, None, None];
+    for (i, flag) in [
+        HAS_NAME,
+        HAS_RELATIVE_PATH,
+        HAS_WORKING_DIR,
+        HAS_ARGUMENTS,
+        HAS_ICON_LOCATION,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if flags & flag == 0 {
+            continue;
+        }
+        let count = r.u16(pos).ok_or_else(truncated)? as usize;
+        pos += 2;
+        let len = if unicode { count * 2 } else { count };
+        let data = bytes.get(pos..pos + len).ok_or_else(truncated)?;
+        strings[i] = Some(if unicode {
+            utf16(data)
+        } else {
+            String::from_utf8_lossy(data).into_owned()
+        })
+        .filter(|s| !s.is_empty());
+        pos += len;
+    }
+    let [_, relative_path, working_dir, arguments, icon_location] = strings;
+    shortcut.relative_path = relative_path;
+    shortcut.working_dir = working_dir;
+    shortcut.arguments = arguments;
+    shortcut.icon_location = icon_location;
+
+    // ExtraData：若干 `BlockSize, BlockSignature, 数据` 块，BlockSize 小于 4 时结束
+    while let (Some(size), Some(signature)) = (r.u32(pos), r.u32(pos + 4)) {
+        let size = size as usize;
+        let Some(block) = bytes.get(pos..pos + size).filter(|_| size >= 8) else {
+            break;
+        };
+        match signature {
+            ENVIRONMENT_VARIABLE_BLOCK => shortcut.env_target = env_block_target(block),
+            ICON_ENVIRONMENT_BLOCK => {
+                if let Some(icon) = env_block_target(block) {
+                    shortcut.icon_location = Some(icon);
+                }
+            }
+            _ => {}
+        }
+        pos += size;
+    }
+    Ok(shortcut)
+}
+
+/// LinkInfo 结构中的目标路径：本地为 `LocalBasePath + CommonPathSuffix`，
+/// 网络为 `NetName\CommonPathSuffix`；头部较长时优先取其中的 Unicode 版本
+fn link_info_target(info: &[u8]) -> Option<String> {
+    let r = Reader { bytes: info };
+    let header_size = r.u32(4)?;
+    let flags = r.u32(8)?;
+    let has_unicode = header_size >= 0x24;
+    let suffix = if has_unicode {
+        r.utf16z(r.u32(0x20)? as usize)
+    } else {
+        r.ansiz(r.u32(0x18)? as usize)
+    }
+    .unwrap_or_default();
+
+    if flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+        let base = if has_unicode {
+            r.utf16z(r.u32(0x1C)? as usize)
+        } else {
+            r.ansiz(r.u32(0x10)? as usize)
+        }?;
+        return Some(base + &suffix);
+    }
+    if flags & COMMON_NETWORK_RELATIVE_LINK != 0 {
+        let offset = r.u32(0x14)? as usize;
+        let link = Reader {
+            bytes: info.get(offset..)?,
+        };
+        let net_name_offset = link.u32(8)? as usize;
+        let net_name = if net_name_offset > 0x14 {
+            link.utf16z(link.u32(0x14)? as usize)
+        } else {
+            link.ansiz(net_name_offset)
+        }?;
+        return Some(if suffix.is_empty() {
+            net_name
+        } else {
+            format!("{}\\{}", net_name.trim_end_matches('\\'), suffix)
+        });
+    }
+    None
+}
+
+/// EnvironmentVariableDataBlock / IconEnvironmentDataBlock：260 字节 ANSI 与 520 字节 Unicode 路径
+fn env_block_target(block: &[u8]) -> Option<String> {
+    let r = Reader { bytes: block };
+    r.utf16z(8 + 260)
+        .filter(|s| !s.is_empty())
+        .or_else(|| r.ansiz(8))
+        .filter(|s| !s.is_empty())
+}
+
+/// 按小端序读取的字节切片；越界时返回 `None`
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn u16(&self, pos: usize) -> Option<u16> {
+        let b = self.bytes.get(pos..pos + 2)?;
+        Some(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        let b = self.bytes.get(pos..pos + 4)?;
+        Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// 以 NUL 结尾的 ANSI 字符串
+    fn ansiz(&self, pos: usize) -> Option<String> {
+        let rest = self.bytes.get(pos..)?;
+        let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        Some(String::from_utf8_lossy(&rest[..end]).into_owned())
+    }
+
+    /// 以 NUL 结尾的 UTF-16LE 字符串
+    fn utf16z(&self, pos: usize) -> Option<String> {
+        let rest = self.bytes.get(pos..)?;
+        let end = rest
+            .chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .map_or(rest.len(), |i| i * 2);
+        Some(utf16(&rest[..end]))
+    }
+}
+
+fn utf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_local_shortcut() {
+        let shortcut = parse(include_bytes!("../../tests/fixtures/app.lnk")).unwrap();
+        assert_eq!(
+            shortcut.link_target.as_deref(),
+            Some(r"C:\Program Files\示例\app.exe")
+        );
+        assert_eq!(
+            shortcut.relative_path.as_deref(),
+            Some(r"..\..\..\..\..\Program Files\示例\app.exe")
+        );
+        assert_eq!(
+            shortcut.working_dir.as_deref(),
+            Some(r"C:\Program Files\示例")
+        );
+        assert_eq!(shortcut.arguments.as_deref(), Some("--minimized --tray"));
+        assert_eq!(
+            shortcut.icon_location.as_deref(),
+            Some(r"%ProgramFiles%\示例\app.ico")
+        );
+        let dir = Path::new(r"C:\Users\alice\Startup");
+        assert_eq!(
+            shortcut.target_in(dir).as_deref(),
+            Some(r"%ProgramFiles%\示例\app.exe")
+        );
+        assert_eq!(
+            shortcut.command_in(dir).as_deref(),
+            Some(r#""%ProgramFiles%\示例\app.exe" --minimized --tray"#)
+        );
+    }
+
+    #[test]
+    fn parses_network_shortcut() {
+        let shortcut = parse(include_bytes!("../../tests/fixtures/share.lnk")).unwrap();
+        assert_eq!(
+            shortcut.link_target.as_deref(),
+            Some(r"\\server\share\tools\run.bat")
+        );
+        assert_eq!(shortcut.arguments.as_deref(), Some("/quiet"));
+        assert_eq!(shortcut.env_target, None);
+        assert_eq!(
+            shortcut.command_in(Path::new(r"C:\Startup")).as_deref(),
+            Some(r#""\\server\share\tools\run.bat" /quiet"#)
+        );
+    }
+
+    #[test]
+    fn resolves_relative_path_against_shortcut_folder() {
+        let shortcut = Shortcut {
+            relative_path: Some(r"..\..\Program Files\App\.\app.exe".to_string()),
+            arguments: Some("-q".to_string()),
+            ..Shortcut::default()
+        };
+        let dir = Path::new(r"C:\ProgramData\Startup\");
+        assert_eq!(
+            shortcut.target_in(dir).as_deref(),
+            Some(r"C:\Program Files\App\app.exe")
+        );
+        assert_eq!(
+            shortcut.command_in(dir).as_deref(),
+            Some(r#""C:\Program Files\App\app.exe" -q"#)
+        );
+        // 不会越过盘符
+        assert_eq!(
+            shortcut.target_in(Path::new("C:")).as_deref(),
+            Some(r"C:\Program Files\App\app.exe")
+        );
+        assert_eq!(Shortcut::default().target_in(dir), None);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(parse(b"not a shortcut").is_err());
+        let fixture = include_bytes!("../../tests/fixtures/app.lnk");
+        assert!(parse(&fixture[..100]).is_err());
+    }
+}
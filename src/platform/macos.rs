@@ -1,13 +1,37 @@
-use crate::platform::helper::{parse_token, OptionItem};
+use crate::config::SourceConfig;
+use crate::platform::btm;
+use crate::platform::command::Runner;
+use crate::platform::helper::{self, parse_token, OptionItem, Scope, Trigger};
+use crate::platform::plist::{self, Value};
+use crate::platform::quarantine;
+use crate::platform::EditTarget;
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 
 /// 启动项类型
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StartupType {
     Plist,
     LoginItem,
+    /// `/System/Library` 下 Apple 自带的 launchd 任务
+    SystemPlist,
+    /// 旧式 `/Library/StartupItems` bundle
+    StartupItem,
+    /// `com.apple.loginwindow` 中的 `LoginHook` / `LogoutHook`
+    LoginHook,
+    /// `/etc/periodic` 等目录中的每日 / 每周 / 每月脚本
+    Periodic,
+    /// `/etc/rc.common`
+    RcCommon,
+    /// 旧式 `/etc/launchd.conf`
+    LaunchdConf,
+    /// `/etc/emond.d/rules` 中的 emond 规则
+    Emond,
+    /// App 包内 `Contents/Library` 下的登录项 helper 与 launchd plist
+    AppBundle,
 }
 
 /// 表示一个 macOS 启动项
@@ -15,92 +39,898 @@ pub enum StartupType {
 pub struct StartupItem {
     pub label: String,
     pub path: Option<String>, // LoginItem 可能没有路径
+    pub command: Option<String>,
+    pub scope: Scope,
+    /// 所在目录；LoginItem 为 `None`，AppBundle 为所属 App
+    pub location: Option<String>,
+    pub modified: Option<SystemTime>,
+    /// 已被禁用（仍保留定义）
+    pub disabled: bool,
+    /// 运行时机；LoginItem 为空（登录时启动）
+    pub triggers: Vec<Trigger>,
+    /// 受 SIP 保护，不能删除或修改
+    pub protected: bool,
+    /// Background Task Management 数据库中记录的开发者
+    pub developer: Option<String>,
+    /// 登录时隐藏窗口（仅 LoginItem）
+    pub hidden: bool,
+    /// 登录项打开的对象种类，取自 System Events（如「应用程序」）
+    pub kind: Option<String>,
     pub item_type: StartupType,
     /// 平台相关的删除令牌，格式：
     /// - Plist: `plist|<文件路径>`
-    /// - LoginItem: `loginitem|<名称>`
+    /// - LoginItem: `loginitem|<路径>`（System Events 未给出路径时为名称）
+    /// - SystemPlist: `sysplist|<文件路径>`
+    /// - StartupItem: `startupitem|<bundle 目录>`
+    /// - LoginHook: `hook|<键>|<偏好设置文件>`
+    /// - 仅见于 BTM 数据库的条目: `btm|<路径或名称>`
+    /// - AppBundle: `bundle|<helper 目录或 plist 路径>`
+    /// - Periodic / RcCommon / LaunchdConf / Emond: `periodic|`、`rc|`、`launchdconf|`、`emond|` 加文件路径
     pub delete_value: String,
 }
 
-/// 获取 macOS 启动项（LaunchAgents & LaunchDaemons）
+/// 获取 macOS 启动项（LaunchAgents & LaunchDaemons），`extra_dirs` 为配置中追加的扫描目录
 #[cfg(target_os = "macos")]
-pub fn get_startup_apps() -> Vec<StartupItem> {
-    let mut items = vec![];
-
+pub fn get_startup_apps(extra_dirs: &[String], runner: &Runner) -> Vec<StartupItem> {
     // 要检查的目录
-    let dirs = vec![
+    let dirs = [
         "~/Library/LaunchAgents", // 用户级
         "/Library/LaunchAgents",  // 系统用户环境
         "/Library/LaunchDaemons", // 系统服务
     ];
+    let dirs: Vec<&str> = dirs
+        .iter()
+        .copied()
+        .chain(extra_dirs.iter().map(String::as_str))
+        .collect();
+    let mut items = scan_plist_dirs(&dirs, StartupType::Plist, &disabled_services(runner));
+    merge_btm_plists(&mut items, &load_btm());
+    items
+}
+
+/// Apple 自带的 launchd 任务（`/System/Library` 下），受 SIP 保护，只读
+#[cfg(target_os = "macos")]
+pub fn get_system_plists(runner: &Runner) -> Vec<StartupItem> {
+    scan_plist_dirs(
+        &[
+            "/System/Library/LaunchAgents",
+            "/System/Library/LaunchDaemons",
+        ],
+        StartupType::SystemPlist,
+        &disabled_services(runner),
+    )
+}
 
+/// 扫描目录中的 launchd plist；无法解析的 plist 仍然列出，Label 退回为文件名（去掉扩展名）
+#[cfg(target_os = "macos")]
+fn scan_plist_dirs(
+    dirs: &[&str],
+    item_type: StartupType,
+    disabled: &BTreeSet<String>,
+) -> Vec<StartupItem> {
+    let mut items = vec![];
+    let home = dirs::home_dir();
+    let kind = match item_type {
+        StartupType::SystemPlist => "sysplist",
+        _ => "plist",
+    };
     for dir in dirs {
         let expanded_path: PathBuf = shellexpand::tilde(dir).into_owned().into();
-        if expanded_path.exists() && expanded_path.is_dir() {
-            if let Ok(entries) = fs::read_dir(expanded_path) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|e| e.to_str()) == Some("plist") {
-                        if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                            let path_str = path.display().to_string();
-                            items.push(StartupItem {
-                                label: file_name.to_string(),
-                                path: Some(path_str.clone()),
-                                item_type: StartupType::Plist,
-                                delete_value: format!("plist|{}", path_str),
-                            });
-                        }
-                    }
-                }
+        // 用户目录下的 plist 只对当前用户生效
+        let scope = match &home {
+            Some(home) if expanded_path.starts_with(home) => Scope::User,
+            _ => Scope::System,
+        };
+        let location = expanded_path.display().to_string();
+        let Ok(entries) = fs::read_dir(&expanded_path) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("plist") {
+                continue;
             }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let path_str = path.display().to_string();
+            let definition = fs::read(&path)
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok());
+            let label = definition
+                .as_ref()
+                .and_then(|d| d.get("Label"))
+                .and_then(Value::as_str)
+                .unwrap_or(stem)
+                .to_string();
+            items.push(StartupItem {
+                disabled: disabled.contains(&label),
+                label,
+                path: Some(path_str.clone()),
+                command: definition.as_ref().and_then(plist::launchd_command),
+                triggers: definition
+                    .as_ref()
+                    .map(plist::launchd_triggers)
+                    .unwrap_or_default(),
+                scope,
+                location: Some(location.clone()),
+                modified: helper::modified_time(&path),
+                protected: item_type == StartupType::SystemPlist || sip_protected(&path),
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type,
+                delete_value: format!("{}|{}", kind, path_str),
+            });
         }
     }
+    items
+}
+
+/// 旧式的 `/Library/StartupItems` 启动项：每个子目录是一个 bundle，
+/// 内含与目录同名的启动脚本及 `StartupParameters.plist`
+#[cfg(target_os = "macos")]
+pub fn get_legacy_startup_items() -> Vec<StartupItem> {
+    let dir = Path::new(STARTUP_ITEMS_DIR);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let script = path.join(&name);
+            let parameters = fs::read(path.join("StartupParameters.plist"))
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok());
+            let description = parameters
+                .as_ref()
+                .and_then(|p| p.get("Description"))
+                .and_then(Value::as_str)
+                .filter(|d| !d.is_empty());
+            let path_str = path.display().to_string();
+            Some(StartupItem {
+                label: description.map_or_else(|| name.clone(), |d| format!("{} ({})", name, d)),
+                command: Some(format!("\"{}\"", script.display())),
+                path: Some(path_str.clone()),
+                scope: Scope::System,
+                location: Some(dir.display().to_string()),
+                modified: helper::modified_time(&path),
+                disabled: false,
+                triggers: vec![],
+                protected: sip_protected(&path),
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type: StartupType::StartupItem,
+                delete_value: format!("startupitem|{}", path_str),
+            })
+        })
+        .collect()
+}
+
+/// 旧式的 StartupItems 目录
+const STARTUP_ITEMS_DIR: &str = "/Library/StartupItems";
+
+/// 可能含 `LoginHook` / `LogoutHook` 的 loginwindow 偏好设置：
+/// `sudo defaults write com.apple.loginwindow` 写入 root 的偏好，也有安装器写入全局偏好
+const LOGINWINDOW_PLISTS: &[&str] = &[
+    "/var/root/Library/Preferences/com.apple.loginwindow.plist",
+    "/Library/Preferences/com.apple.loginwindow.plist",
+];
 
+/// loginwindow 在用户登录 / 注销时以 root 身份运行的脚本
+const HOOK_KEYS: &[&str] = &["LoginHook", "LogoutHook"];
+
+/// `com.apple.loginwindow` 中的 `LoginHook` / `LogoutHook`。
+/// root 的偏好设置通常无权读取，读不到时跳过
+#[cfg(target_os = "macos")]
+pub fn get_login_hooks() -> Vec<StartupItem> {
+    let mut items = vec![];
+    for plist_path in LOGINWINDOW_PLISTS {
+        let Some(prefs) = fs::read(plist_path)
+            .ok()
+            .and_then(|bytes| plist::parse(&bytes).ok())
+        else {
+            continue;
+        };
+        for (key, script) in login_hooks(&prefs) {
+            let modified = helper::modified_time(Path::new(plist_path));
+            items.push(StartupItem {
+                label: key.to_string(),
+                command: Some(format!("\"{}\"", script)),
+                path: Some(script),
+                scope: Scope::System,
+                location: Some(plist_path.to_string()),
+                modified,
+                disabled: false,
+                triggers: vec![],
+                protected: false,
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type: StartupType::LoginHook,
+                delete_value: format!("hook|{}|{}", key, plist_path),
+            });
+        }
+    }
     items
 }
 
-/// 获取 macOS 登录项（Login Items）
-#[cfg(target_os = "macos")]
-pub fn get_login_items() -> Vec<StartupItem> {
-    let output = Command::new("osascript")
-        .arg("-e")
-        .arg(r#"tell application "System Events" to get the name of every login item"#)
-        .output();
-
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                let stdout = String::from_utf8_lossy(&result.stdout);
-                stdout
-                    .split(", ")
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .map(|label| StartupItem {
-                        delete_value: format!("loginitem|{}", label),
-                        label,
-                        path: None,
-                        item_type: StartupType::LoginItem,
-                    })
-                    .collect()
-            } else {
-                eprintln!("osascript error: {:?}", result.stderr);
-                vec![]
+/// 偏好设置中设置了的 hook：`(键, 脚本路径)`
+fn login_hooks(prefs: &Value) -> Vec<(&'static str, String)> {
+    HOOK_KEYS
+        .iter()
+        .filter_map(|&key| {
+            let script = prefs.get(key)?.as_str()?.trim();
+            (!script.is_empty()).then(|| (key, script.to_string()))
+        })
+        .collect()
+}
+
+/// `periodic(8)` 每天 / 每周 / 每月执行的脚本目录（系统自带的与 Homebrew 等安装的）
+const PERIODIC_DIRS: &[&str] = &[
+    "/etc/periodic/daily",
+    "/etc/periodic/weekly",
+    "/etc/periodic/monthly",
+    "/usr/local/etc/periodic/daily",
+    "/usr/local/etc/periodic/weekly",
+    "/usr/local/etc/periodic/monthly",
+];
+
+/// `periodic` 脚本：只有可执行的文件会被运行，因此去掉执行权限即为禁用
+#[cfg(target_os = "macos")]
+pub fn get_periodic_scripts() -> Vec<StartupItem> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut items = vec![];
+    for dir in PERIODIC_DIRS {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_file()) {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let path_str = path.display().to_string();
+            let executable = path
+                .metadata()
+                .is_ok_and(|m| m.permissions().mode() & 0o111 != 0);
+            items.push(StartupItem {
+                label: name.to_string(),
+                command: Some(format!("\"{}\"", path_str)),
+                path: Some(path_str.clone()),
+                scope: Scope::System,
+                location: Some(dir.to_string()),
+                modified: helper::modified_time(&path),
+                disabled: !executable,
+                triggers: vec![Trigger::Calendar],
+                protected: false,
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type: StartupType::Periodic,
+                delete_value: format!("periodic|{}", path_str),
+            });
+        }
+    }
+    items
+}
+
+/// 旧式的系统级配置文件：`rc.common` 在启动脚本中被 source，`launchd.conf` 在 10.10 之前由 launchd
+/// 在开机时执行（新系统已忽略，但出现即值得检查）。文件存在时各列为一项
+#[cfg(target_os = "macos")]
+pub fn get_config_file(path: &str, item_type: StartupType) -> Vec<StartupItem> {
+    let path = Path::new(path);
+    if !path.is_file() {
+        return vec![];
+    }
+    let kind = match item_type {
+        StartupType::RcCommon => "rc",
+        _ => "launchdconf",
+    };
+    let path_str = path.display().to_string();
+    vec![StartupItem {
+        label: path
+            .file_name()
+            .map_or_else(|| path_str.clone(), |n| n.to_string_lossy().into_owned()),
+        path: Some(path_str.clone()),
+        command: None,
+        scope: Scope::System,
+        location: path.parent().map(|p| p.display().to_string()),
+        modified: helper::modified_time(path),
+        disabled: false,
+        triggers: vec![],
+        protected: false,
+        developer: None,
+        hidden: false,
+        kind: None,
+        item_type,
+        delete_value: format!("{}|{}", kind, path_str),
+    }]
+}
+
+/// emond 的规则目录，每个 plist 含一条或多条规则
+const EMOND_RULES_DIR: &str = "/etc/emond.d/rules";
+
+/// emond 规则文件：事件发生时执行规则中的 `RunCommand` 动作
+#[cfg(target_os = "macos")]
+pub fn get_emond_rules() -> Vec<StartupItem> {
+    let Ok(entries) = fs::read_dir(EMOND_RULES_DIR) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("plist"))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let rules = fs::read(&path)
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok())
+                .map(|value| emond_rules(&value))
+                .unwrap_or_default();
+            let names: Vec<&str> = rules.iter().filter_map(|r| r.name.as_deref()).collect();
+            let path_str = path.display().to_string();
+            Some(StartupItem {
+                label: if names.is_empty() {
+                    stem
+                } else {
+                    names.join(", ")
+                },
+                path: Some(path_str.clone()),
+                command: rules.iter().find_map(|r| r.command.clone()),
+                scope: Scope::System,
+                location: Some(EMOND_RULES_DIR.to_string()),
+                modified: helper::modified_time(&path),
+                disabled: !rules.is_empty() && rules.iter().all(|r| !r.enabled),
+                triggers: vec![],
+                protected: false,
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type: StartupType::Emond,
+                delete_value: format!("emond|{}", path_str),
+            })
+        })
+        .collect()
+}
+
+/// 一条 emond 规则中需要展示的部分
+#[derive(Debug, PartialEq, Eq)]
+struct EmondRule {
+    name: Option<String>,
+    enabled: bool,
+    /// 第一个 `RunCommand` 动作的命令行
+    command: Option<String>,
+}
+
+/// 解析规则文件：顶层为规则数组（也接受单条规则的字典）
+fn emond_rules(value: &Value) -> Vec<EmondRule> {
+    let rules = match value {
+        Value::Array(rules) => rules.as_slice(),
+        Value::Dict(_) => std::slice::from_ref(value),
+        _ => &[],
+    };
+    rules
+        .iter()
+        .map(|rule| {
+            let command = rule
+                .get("actions")
+                .and_then(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .filter(|action| action.get("type").and_then(Value::as_str) == Some("RunCommand"))
+                .find_map(|action| {
+                    let program = action.get("command")?.as_str()?;
+                    let args = action
+                        .get("arguments")
+                        .and_then(Value::as_array)
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(Value::as_str);
+                    let parts: Vec<String> = std::iter::once(program)
+                        .chain(args)
+                        .map(|s| {
+                            if s.contains(char::is_whitespace) {
+                                format!("\"{}\"", s)
+                            } else {
+                                s.to_string()
+                            }
+                        })
+                        .collect();
+                    Some(parts.join(" "))
+                });
+            EmondRule {
+                name: rule.get("name").and_then(Value::as_str).map(str::to_string),
+                // 未写 `enabled` 的规则默认生效
+                enabled: rule.get("enabled") != Some(&Value::Boolean(false)),
+                command,
+            }
+        })
+        .collect()
+}
+
+/// 路径是否受系统完整性保护（SIP）：`/System`、`/bin`、`/sbin` 与 `/usr`（`/usr/local` 除外）。
+/// 这些位置即使以 root 身份也无法修改，删除与禁用直接拒绝
+fn sip_protected(path: &Path) -> bool {
+    ["/System", "/bin", "/sbin", "/usr"]
+        .iter()
+        .any(|root| path.starts_with(root))
+        && !path.starts_with("/usr/local")
+}
+
+/// 被 `launchctl disable`（或 `unload -w`）禁用的服务名，合并系统域与当前用户的 GUI 域
+#[cfg(target_os = "macos")]
+fn disabled_services(runner: &Runner) -> BTreeSet<String> {
+    let uid = runner
+        .output(Command::new("id").arg("-u"))
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+    let mut domains = vec!["system".to_string()];
+    if !uid.is_empty() {
+        domains.push(format!("gui/{}", uid));
+    }
+    domains
+        .iter()
+        .filter_map(|domain| {
+            runner
+                .output(Command::new("launchctl").args(["print-disabled", domain]))
+                .ok()
+        })
+        .flat_map(|output| parse_disabled(&String::from_utf8_lossy(&output.stdout)))
+        .collect()
+}
+
+/// 解析 `launchctl print-disabled` 的输出，形如 `"com.example.agent" => disabled`
+/// （旧版系统为 `=> true`），返回被禁用的服务名
+fn parse_disabled(output: &str) -> BTreeSet<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once("=>"))
+        .filter(|(_, state)| matches!(state.trim(), "disabled" | "true"))
+        .map(|(name, _)| name.trim().trim_matches('"').to_string())
+        .collect()
+}
+
+/// 列出登录项的 AppleScript：每项输出 名称、路径、是否隐藏、种类，
+/// 字段以 US（0x1F）、记录以 RS（0x1E）分隔，名称中的逗号与换行不会打乱结果；
+/// 缺失的路径或种类输出为空
+const LOGIN_ITEMS_SCRIPT: &str = r#"set fs to character id 31
+set rs to character id 30
+set out to ""
+tell application "System Events"
+    repeat with li in every login item
+        set p to ""
+        try
+            set p to (path of li) as text
+        end try
+        set k to ""
+        try
+            set k to (kind of li) as text
+        end try
+        set out to out & (name of li) & fs & p & fs & ((hidden of li) as text) & fs & k & rs
+    end repeat
+end tell
+return out"#;
+
+/// System Events 中的一个登录项
+#[derive(Debug, PartialEq, Eq)]
+struct LoginItemRecord {
+    name: String,
+    path: Option<String>,
+    hidden: bool,
+    kind: Option<String>,
+}
+
+/// 解析 `LOGIN_ITEMS_SCRIPT` 的输出；AppleScript 的 `missing value` 视为缺失
+fn parse_login_items(output: &str) -> Vec<LoginItemRecord> {
+    let field = |s: &str| {
+        let s = s.trim();
+        (!s.is_empty() && s != "missing value").then(|| s.to_string())
+    };
+    output
+        .trim_end_matches(['\n', '\r'])
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let mut fields = record.split('\u{1f}');
+            let name = fields.next().and_then(field)?;
+            Some(LoginItemRecord {
+                name,
+                path: fields.next().and_then(field),
+                hidden: fields.next().map(str::trim) == Some("true"),
+                kind: fields.next().and_then(field),
+            })
+        })
+        .collect()
+}
+
+/// 获取 macOS 登录项（Login Items）及其路径、是否隐藏与种类。`osascript` 可能因等待自动化权限而挂起，
+/// 超时或失败时返回错误
+#[cfg(target_os = "macos")]
+pub fn get_login_items(runner: &Runner) -> Result<Vec<StartupItem>, String> {
+    let result = runner
+        .output(Command::new("osascript").arg("-e").arg(LOGIN_ITEMS_SCRIPT))
+        .map_err(|e| format!("无法运行 osascript: {}", e))?;
+    if !result.status.success() {
+        let err = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("osascript 出错: {}", err.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let mut items: Vec<StartupItem> = parse_login_items(&stdout)
+        .into_iter()
+        .map(LoginItemRecord::into_item)
+        .collect();
+    merge_btm_login_items(&mut items, &load_btm());
+    Ok(items)
+}
+
+impl LoginItemRecord {
+    /// 转为启动项：有路径时按路径删除，以区分同名的登录项
+    fn into_item(self) -> StartupItem {
+        let path = self.path.as_deref().map(Path::new);
+        StartupItem {
+            delete_value: format!("loginitem|{}", self.path.as_deref().unwrap_or(&self.name)),
+            label: self.name,
+            command: self.path.as_ref().map(|p| format!("\"{}\"", p)),
+            scope: Scope::User,
+            location: path.and_then(Path::parent).map(|p| p.display().to_string()),
+            modified: path.and_then(helper::modified_time),
+            disabled: false,
+            triggers: vec![],
+            protected: false,
+            developer: None,
+            hidden: self.hidden,
+            kind: self.kind,
+            item_type: StartupType::LoginItem,
+            path: self.path,
+        }
+    }
+}
+
+/// 查找 App 的目录：其中的 `.app` 以及下一层子目录（如 `/Applications/Utilities`）中的 `.app`
+const APP_DIRS: &[&str] = &["/Applications", "~/Applications"];
+
+/// App 包内嵌的启动项：`SMLoginItemSetEnabled` 使用的 `Contents/Library/LoginItems` 下的 helper，
+/// 以及 `SMAppService` 使用的 `Contents/Library/LaunchAgents` / `LaunchDaemons` 下的 plist；
+/// 按 BTM 数据库与 `launchctl print-disabled` 标出开发者与禁用状态
+#[cfg(target_os = "macos")]
+pub fn get_app_bundle_helpers(runner: &Runner) -> Vec<StartupItem> {
+    let disabled = disabled_services(runner);
+    let records = load_btm();
+    APP_DIRS
+        .iter()
+        .map(|dir| PathBuf::from(shellexpand::tilde(dir).into_owned()))
+        .flat_map(|dir| app_bundles(&dir))
+        .flat_map(|app| bundle_helpers(&app, &disabled, &records))
+        .collect()
+}
+
+/// 目录中的 `.app`；不是 `.app` 的子目录再向下找一层
+fn app_bundles(dir: &Path) -> Vec<PathBuf> {
+    let is_app = |path: &Path| path.extension().and_then(|e| e.to_str()) == Some("app");
+    let entries = |dir: &Path| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect()
+    };
+    let mut apps = vec![];
+    for path in entries(dir) {
+        if is_app(&path) {
+            apps.push(path);
+        } else {
+            apps.extend(entries(&path).into_iter().filter(|p| is_app(p)));
+        }
+    }
+    apps.sort();
+    apps
+}
+
+/// 单个 App 包内嵌的登录项 helper 与 launchd plist
+fn bundle_helpers(
+    app: &Path,
+    disabled: &BTreeSet<String>,
+    records: &[btm::Item],
+) -> Vec<StartupItem> {
+    let library = app.join("Contents/Library");
+    let location = Some(app.display().to_string());
+    let mut items = vec![];
+
+    let helpers = fs::read_dir(library.join("LoginItems"))
+        .into_iter()
+        .flatten();
+    for helper in helpers.flatten().map(|e| e.path()) {
+        if helper.extension().and_then(|e| e.to_str()) != Some("app") {
+            continue;
+        }
+        let Some(stem) = helper.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let info = fs::read(helper.join("Contents/Info.plist"))
+            .ok()
+            .and_then(|bytes| plist::parse(&bytes).ok());
+        let info_str = |key: &str| {
+            info.as_ref()
+                .and_then(|i| i.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let executable = info_str("CFBundleExecutable").unwrap_or_else(|| stem.to_string());
+        // SMLoginItemSetEnabled 以 helper 的 Bundle ID 作为 launchd 标签
+        let bundle_id = info_str("CFBundleIdentifier");
+        let path_str = helper.display().to_string();
+        items.push(StartupItem {
+            label: stem.to_string(),
+            command: Some(format!(
+                "\"{}\"",
+                helper.join("Contents/MacOS").join(executable).display()
+            )),
+            scope: Scope::User,
+            location: location.clone(),
+            modified: helper::modified_time(&helper),
+            disabled: bundle_id.as_ref().is_some_and(|id| disabled.contains(id)),
+            triggers: vec![],
+            protected: false,
+            developer: None,
+            hidden: false,
+            kind: None,
+            item_type: StartupType::AppBundle,
+            delete_value: format!("bundle|{}", path_str),
+            path: Some(path_str),
+        });
+        if let Some(id) = bundle_id {
+            link_btm(items.last_mut().expect("刚追加的项"), &id, records);
+        }
+    }
+
+    for (dir, scope) in [
+        ("LaunchAgents", Scope::User),
+        ("LaunchDaemons", Scope::System),
+    ] {
+        let plists = fs::read_dir(library.join(dir)).into_iter().flatten();
+        for path in plists.flatten().map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some("plist") {
+                continue;
             }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let definition = fs::read(&path)
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok());
+            let label = definition
+                .as_ref()
+                .and_then(|d| d.get("Label"))
+                .and_then(Value::as_str)
+                .unwrap_or(stem)
+                .to_string();
+            // SMAppService 的 plist 以 `BundleProgram` 给出相对于 App 包的可执行文件
+            let command = definition
+                .as_ref()
+                .and_then(plist::launchd_command)
+                .or_else(|| {
+                    definition
+                        .as_ref()
+                        .and_then(|d| d.get("BundleProgram"))
+                        .and_then(Value::as_str)
+                        .map(|program| format!("\"{}\"", app.join(program).display()))
+                });
+            let path_str = path.display().to_string();
+            items.push(StartupItem {
+                disabled: disabled.contains(&label),
+                command,
+                triggers: definition
+                    .as_ref()
+                    .map(plist::launchd_triggers)
+                    .unwrap_or_default(),
+                scope,
+                location: location.clone(),
+                modified: helper::modified_time(&path),
+                protected: false,
+                developer: None,
+                hidden: false,
+                kind: None,
+                item_type: StartupType::AppBundle,
+                delete_value: format!("bundle|{}", path_str),
+                path: Some(path_str),
+                label: label.clone(),
+            });
+            link_btm(items.last_mut().expect("刚追加的项"), &label, records);
         }
-        Err(err) => {
-            eprintln!("Failed to run osascript: {}", err);
-            vec![]
+    }
+    items
+}
+
+/// 按路径或标识符（BTM 中为 `<类型>.<标签>`，如 `16.com.example.agent`）找到对应的 BTM 条目，
+/// 补上开发者；在系统设置中被关闭的标为禁用
+fn link_btm(item: &mut StartupItem, label: &str, records: &[btm::Item]) {
+    let record = records.iter().find(|r| {
+        (r.path.is_some() && r.path == item.path)
+            || r.identifier
+                .as_deref()
+                .and_then(|id| id.split_once('.'))
+                .is_some_and(|(_, id)| id == label)
+    });
+    if let Some(record) = record {
+        item.developer = record.developer.clone();
+        item.disabled |= !record.enabled;
+    }
+}
+
+/// 位于 App 包内的定义（由 AppBundle 来源列出，BTM 合并时跳过）
+fn in_app_bundle(path: &str) -> bool {
+    path.contains(".app/Contents/Library/")
+}
+
+/// Background Task Management 数据库所在目录（macOS 13 起），只有 root 可读
+const BTM_DIR: &str = "/private/var/db/com.apple.backgroundtaskmanagement";
+
+/// 读取版本号最高的 `BackgroundItems-v*.btm`；无权读取或格式无法识别时为空
+#[cfg(target_os = "macos")]
+fn load_btm() -> Vec<btm::Item> {
+    let names = fs::read_dir(BTM_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| e.file_name().to_str().map(str::to_string));
+    latest_btm(names)
+        .and_then(|name| fs::read(Path::new(BTM_DIR).join(name)).ok())
+        .and_then(|bytes| btm::parse(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// `BackgroundItems-v<n>.btm` 中 n 最大的文件名
+fn latest_btm(names: impl Iterator<Item = String>) -> Option<String> {
+    names
+        .filter_map(|name| {
+            let version: u32 = name
+                .strip_prefix("BackgroundItems-v")?
+                .strip_suffix(".btm")?
+                .parse()
+                .ok()?;
+            Some((version, name))
+        })
+        .max()
+        .map(|(_, name)| name)
+}
+
+/// 用 BTM 数据库补充 plist 扫描结果：已扫描到的项补上开发者，在系统设置中被关闭的标为禁用；
+/// 只登记在 BTM 中的 agent / daemon 追加为新项；定义位于 App 包内的由 AppBundle 来源列出
+fn merge_btm_plists(items: &mut Vec<StartupItem>, records: &[btm::Item]) {
+    let home = dirs::home_dir();
+    for record in records
+        .iter()
+        .filter(|r| matches!(r.kind, btm::Kind::Agent | btm::Kind::Daemon))
+        .filter(|r| !r.path.as_deref().is_some_and(in_app_bundle))
+    {
+        if let Some(item) = items
+            .iter_mut()
+            .find(|item| item.path.is_some() && item.path == record.path)
+        {
+            item.developer = record.developer.clone();
+            item.disabled |= !record.enabled;
+            continue;
         }
+        let definition = record
+            .path
+            .as_ref()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| plist::parse(&bytes).ok());
+        let path = record.path.as_deref().map(Path::new);
+        items.push(StartupItem {
+            label: definition
+                .as_ref()
+                .and_then(|d| d.get("Label"))
+                .and_then(Value::as_str)
+                .unwrap_or(&record.name)
+                .to_string(),
+            path: record.path.clone(),
+            command: definition
+                .as_ref()
+                .and_then(plist::launchd_command)
+                .or_else(|| record.executable.as_ref().map(|e| format!("\"{}\"", e))),
+            scope: match (&home, path) {
+                (Some(home), Some(path)) if path.starts_with(home) => Scope::User,
+                _ => Scope::System,
+            },
+            location: path.and_then(Path::parent).map(|p| p.display().to_string()),
+            modified: path.and_then(helper::modified_time),
+            disabled: !record.enabled,
+            triggers: definition
+                .as_ref()
+                .map(plist::launchd_triggers)
+                .unwrap_or_default(),
+            protected: false,
+            developer: record.developer.clone(),
+            hidden: false,
+            kind: None,
+            item_type: StartupType::Plist,
+            delete_value: format!("btm|{}", record.path.as_deref().unwrap_or(&record.name)),
+        });
+    }
+}
+
+/// 用 BTM 数据库补充登录项：按路径或名称匹配已列出的项，补上路径、开发者与启用状态；
+/// System Events 看不到的（如 SMAppService 注册的登录项）追加为新项
+fn merge_btm_login_items(items: &mut Vec<StartupItem>, records: &[btm::Item]) {
+    for record in records
+        .iter()
+        .filter(|r| matches!(r.kind, btm::Kind::App | btm::Kind::LoginItem))
+        .filter(|r| !r.path.as_deref().is_some_and(in_app_bundle))
+    {
+        let app_name = record
+            .path
+            .as_deref()
+            .and_then(|p| Path::new(p).file_stem())
+            .and_then(|s| s.to_str());
+        // 有路径时按路径匹配，否则按名称或 App 名
+        let matched = items
+            .iter_mut()
+            .find(|item| match (&item.path, &record.path) {
+                (Some(path), Some(record_path)) => path == record_path,
+                _ => item.label == record.name || Some(item.label.as_str()) == app_name,
+            });
+        let item = match matched {
+            Some(item) => item,
+            None => {
+                items.push(StartupItem {
+                    label: record.name.clone(),
+                    path: None,
+                    command: None,
+                    scope: Scope::User,
+                    location: None,
+                    modified: None,
+                    disabled: false,
+                    triggers: vec![],
+                    protected: false,
+                    developer: None,
+                    hidden: false,
+                    kind: None,
+                    item_type: StartupType::LoginItem,
+                    delete_value: format!("btm|{}", record.path.as_deref().unwrap_or(&record.name)),
+                });
+                items.last_mut().expect("刚追加的项")
+            }
+        };
+        item.path = item.path.take().or_else(|| record.path.clone());
+        item.command = item
+            .command
+            .take()
+            .or_else(|| record.executable.as_ref().map(|e| format!("\"{}\"", e)));
+        item.developer = record.developer.clone();
+        item.disabled |= !record.enabled;
     }
 }
 
-/// 合并获取所有启动项
+/// 扫描单个来源，`id` 为 `platform::SOURCES` 中的来源 ID
 #[cfg(target_os = "macos")]
-pub fn get_all_startup_items() -> Vec<StartupItem> {
-    let mut all = vec![];
-    all.extend(get_startup_apps());
-    all.extend(get_login_items());
-    all
+pub fn scan_source(
+    id: &str,
+    source: &SourceConfig,
+    runner: &Runner,
+) -> Result<Vec<StartupItem>, String> {
+    match id {
+        "plist" => Ok(get_startup_apps(&source.extra_dirs, runner)),
+        "login_item" => get_login_items(runner),
+        "system_plist" => Ok(get_system_plists(runner)),
+        "startup_items" => Ok(get_legacy_startup_items()),
+        "login_hook" => Ok(get_login_hooks()),
+        "periodic" => Ok(get_periodic_scripts()),
+        "rc_common" => Ok(get_config_file("/etc/rc.common", StartupType::RcCommon)),
+        "launchd_conf" => Ok(get_config_file(
+            "/etc/launchd.conf",
+            StartupType::LaunchdConf,
+        )),
+        "emond" => Ok(get_emond_rules()),
+        "app_bundle" => Ok(get_app_bundle_helpers(runner)),
+        _ => Err(format!("未知来源: {}", id)),
+    }
 }
 
 /// 转义 AppleScript 字符串中的特殊字符，避免注入
@@ -109,10 +939,47 @@ fn escape_applescript_string(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
+/// 受 SIP 保护的启动项、只登记在 BTM 中的条目与 App 包内的 helper：拒绝删除、禁用与编辑
+fn refuse_protected(item: &OptionItem) -> Result<(), String> {
+    let (kind, payload) = parse_token(&item.value);
+    let protected = match kind {
+        "sysplist" => true,
+        "plist" | "startupitem" => sip_protected(Path::new(payload)),
+        _ => false,
+    };
+    if protected {
+        Err(format!(
+            "受系统完整性保护（SIP）的启动项只读，无法修改: {}",
+            item.label
+        ))
+    } else if kind == "btm" {
+        // 由 App 通过 SMAppService 注册，定义在 App 包内，改动会破坏签名
+        Err(format!(
+            "{} 由应用在后台任务管理中注册，请在「系统设置 > 通用 > 登录项」中关闭",
+            item.label
+        ))
+    } else if kind == "bundle" {
+        // 改动 App 包内的文件会破坏签名
+        Err(format!(
+            "{} 位于应用包内，请在「系统设置 > 通用 > 登录项」中关闭或卸载所属应用",
+            item.label
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 /// 删除指定的开机启动项。
-/// 参数：item - 要删除的启动项，其 `value` 为 `plist|<路径>` 或 `loginitem|<名称>`。
+/// 参数：item - 要删除的启动项，其 `value` 为删除令牌（见 `StartupItem::delete_value`）；
+/// quarantine - 隔离目录，设置时 plist 与 StartupItems bundle 移入该目录而非直接删除，
+/// 登录项与 hook 先记录名称或脚本路径备份。受 SIP 保护的启动项拒绝删除。
 #[cfg(target_os = "macos")]
-pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::Error>> {
+pub fn delete_startup_item(
+    item: &OptionItem,
+    quarantine: Option<&Path>,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    refuse_protected(item)?;
     // 令牌格式：<kind>|<payload>，其中 payload 可能自身包含 `|`（如路径/名称）
     let (kind, payload) = parse_token(&item.value);
 
@@ -120,21 +987,40 @@ pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::
         "plist" => {
             let path = payload;
             // 先卸载，再删除文件；任一步失败均向上返回错误而非 panic
-            let unload = Command::new("launchctl").arg("unload").arg(path).output()?;
+            let unload = runner.output(Command::new("launchctl").arg("unload").arg(path))?;
             if !unload.status.success() {
                 let err = String::from_utf8_lossy(&unload.stderr);
                 return Err(format!("卸载 Plist 失败 ({}): {}", item.label, err.trim()).into());
             }
-            fs::remove_file(path)?;
+            match quarantine {
+                Some(dir) => {
+                    quarantine::move_file(dir, Path::new(path))?;
+                }
+                None => fs::remove_file(path)?,
+            }
         }
+        // payload 为路径时删除该路径的登录项，同名的其它登录项保留
         "loginitem" => {
-            let name = payload;
-            let escaped = escape_applescript_string(name);
-            let script = format!(
-                r#"tell application "System Events" to delete login item "{}""#,
-                escaped
-            );
-            let output = Command::new("osascript").arg("-e").arg(script).output()?;
+            if let Some(dir) = quarantine {
+                let file_name = format!(
+                    "{}.loginitem.txt",
+                    quarantine::sanitize_file_name(&item.label)
+                );
+                quarantine::write_backup(dir, &item.value, &file_name, payload.as_bytes())?;
+            }
+            let escaped = escape_applescript_string(payload);
+            let script = if payload.starts_with('/') {
+                format!(
+                    r#"tell application "System Events" to delete (every login item whose path is "{}")"#,
+                    escaped
+                )
+            } else {
+                format!(
+                    r#"tell application "System Events" to delete login item "{}""#,
+                    escaped
+                )
+            };
+            let output = runner.output(Command::new("osascript").arg("-e").arg(script))?;
             if !output.status.success() {
                 let err = String::from_utf8_lossy(&output.stderr);
                 return Err(
@@ -142,6 +1028,48 @@ pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::
                 );
             }
         }
+        // StartupItems 没有 launchd 状态可卸载，移走整个 bundle 即可
+        "startupitem" => match quarantine {
+            Some(dir) => {
+                quarantine::move_file(dir, Path::new(payload))?;
+            }
+            None => fs::remove_dir_all(payload)?,
+        },
+        "periodic" | "launchdconf" | "emond" => match quarantine {
+            Some(dir) => {
+                quarantine::move_file(dir, Path::new(payload))?;
+            }
+            None => fs::remove_file(payload)?,
+        },
+        "rc" => {
+            return Err(
+                format!("{} 是系统启动脚本，不能删除，请编辑其中的内容", item.label).into(),
+            );
+        }
+        // 删除偏好设置中的键，脚本本身保留
+        "hook" => {
+            let (key, prefs) = payload.split_once('|').unwrap_or((payload, ""));
+            let script = fs::read(prefs)
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok())
+                .and_then(|p| p.get(key).and_then(Value::as_str).map(str::to_string))
+                .unwrap_or_default();
+            if let Some(dir) = quarantine {
+                let contents = format!("sudo defaults write {} {} \"{}\"\n", prefs, key, script);
+                quarantine::write_backup(
+                    dir,
+                    &item.value,
+                    &format!("{}.txt", key),
+                    contents.as_bytes(),
+                )?;
+            }
+            let output =
+                runner.output(Command::new("defaults").arg("delete").arg(prefs).arg(key))?;
+            if !output.status.success() {
+                let err = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("删除 {} 失败: {}", key, err.trim()).into());
+            }
+        }
         _ => {
             return Err(format!("不支持的启动项类型: {}", item.value).into());
         }
@@ -149,9 +1077,399 @@ pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+/// 禁用指定的开机启动项：plist 通过 `launchctl unload -w` 卸载并写入禁用标记，
+/// 文件保留在原处；periodic 脚本去掉执行权限；其它类型没有禁用状态，只能删除或编辑；
+/// 受 SIP 保护的启动项拒绝禁用。
+#[cfg(target_os = "macos")]
+pub fn disable_startup_item(
+    item: &OptionItem,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    refuse_protected(item)?;
+    let (kind, payload) = parse_token(&item.value);
+    match kind {
+        "plist" => {
+            let output =
+                runner.output(Command::new("launchctl").args(["unload", "-w", payload]))?;
+            if !output.status.success() {
+                let err = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("禁用 Plist 失败 ({}): {}", item.label, err.trim()).into());
+            }
+            Ok(())
+        }
+        "loginitem" => Err(format!("登录项不支持禁用，请使用删除: {}", item.label).into()),
+        "startupitem" => Err(format!("StartupItems 不支持禁用，请使用删除: {}", item.label).into()),
+        "hook" => Err(format!("{} 不支持禁用，请使用删除", item.label).into()),
+        // periodic 只运行可执行的脚本
+        "periodic" => {
+            use std::os::unix::fs::PermissionsExt;
+            let mut permissions = fs::metadata(payload)?.permissions();
+            permissions.set_mode(permissions.mode() & !0o111);
+            fs::set_permissions(payload, permissions)
+                .map_err(|e| format!("禁用 {} 失败: {}", item.label, e).into())
+        }
+        "rc" | "launchdconf" | "emond" => {
+            Err(format!("{} 不支持禁用，请编辑或删除", item.label).into())
+        }
+        _ => Err(format!("不支持的启动项类型: {}", item.value).into()),
+    }
+}
+
+/// 可在编辑器中打开的定义：Plist、periodic 脚本、系统配置文件与 emond 规则直接编辑文件，
+/// StartupItems 编辑 `StartupParameters.plist`，hook 编辑其脚本；登录项没有定义文件，
+/// 受 SIP 保护的文件只读
+#[cfg(target_os = "macos")]
+pub fn edit_target(item: &OptionItem) -> Result<EditTarget, String> {
+    refuse_protected(item)?;
+    let (kind, payload) = parse_token(&item.value);
+    match kind {
+        "plist" => Ok(EditTarget::File(PathBuf::from(payload))),
+        "loginitem" => Err(format!("登录项没有可编辑的定义文件: {}", item.label)),
+        "startupitem" => Ok(EditTarget::File(
+            Path::new(payload).join("StartupParameters.plist"),
+        )),
+        "periodic" | "rc" | "launchdconf" | "emond" => Ok(EditTarget::File(PathBuf::from(payload))),
+        "hook" => {
+            let (key, prefs) = payload.split_once('|').unwrap_or((payload, ""));
+            fs::read(prefs)
+                .ok()
+                .and_then(|bytes| plist::parse(&bytes).ok())
+                .and_then(|p| p.get(key).and_then(Value::as_str).map(PathBuf::from))
+                .map(EditTarget::File)
+                .ok_or_else(|| format!("无法读取 {} 的脚本路径: {}", key, prefs))
+        }
+        _ => Err(format!("不支持的启动项类型: {}", item.value)),
+    }
+}
+
+/// 用 `plutil -lint` 校验编辑后的 plist；其它文件（如 hook 脚本）不校验
+#[cfg(target_os = "macos")]
+pub fn validate_edit(target: &EditTarget, runner: &Runner) -> Result<(), String> {
+    let EditTarget::File(path) = target else {
+        return Err("macOS 不支持导入注册表".to_string());
+    };
+    if path.extension().and_then(|e| e.to_str()) != Some("plist") {
+        return Ok(());
+    }
+    let output = runner
+        .output(Command::new("plutil").arg("-lint").arg(path))
+        .map_err(|e| format!("无法运行 plutil: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let message = String::from_utf8_lossy(&output.stdout).into_owned()
+            + &String::from_utf8_lossy(&output.stderr);
+        Err(message.trim().to_string())
+    }
+}
+
+/// plist 中 `StandardOutPath` / `StandardErrorPath` 指向的日志文件（两者相同时只返回一个）
+#[cfg(target_os = "macos")]
+pub fn log_files(item: &OptionItem, _runner: &Runner) -> Result<Vec<PathBuf>, String> {
+    let (kind, payload) = parse_token(&item.value);
+    match kind {
+        "plist" | "sysplist" => {}
+        "btm" | "bundle" if payload.ends_with(".plist") => {}
+        "loginitem" | "startupitem" | "hook" | "periodic" | "rc" | "launchdconf" | "emond"
+        | "btm" | "bundle" => return Ok(vec![]),
+        _ => return Err(format!("不支持的启动项类型: {}", item.value)),
+    }
+    let bytes = fs::read(payload).map_err(|e| format!("无法读取 {}: {}", payload, e))?;
+    let definition = plist::parse(&bytes)?;
+    Ok(plist::launchd_log_paths(&definition)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::escape_applescript_string;
+    use super::*;
+
+    #[test]
+    fn sip_protected_paths() {
+        assert!(sip_protected(Path::new(
+            "/System/Library/LaunchDaemons/com.apple.foo.plist"
+        )));
+        assert!(sip_protected(Path::new("/usr/libexec/foo")));
+        assert!(!sip_protected(Path::new("/usr/local/bin/foo")));
+        assert!(!sip_protected(Path::new("/Library/LaunchDaemons/a.plist")));
+        assert!(!sip_protected(Path::new("/Systemic/a.plist")));
+    }
+
+    #[test]
+    fn protected_items_are_refused() {
+        let item = |value: &str| OptionItem {
+            label: "x".to_string(),
+            value: value.to_string(),
+        };
+        assert!(refuse_protected(&item("sysplist|/tmp/a.plist")).is_err());
+        assert!(refuse_protected(&item("plist|/System/Library/LaunchAgents/a.plist")).is_err());
+        assert!(refuse_protected(&item("plist|/Library/LaunchAgents/a.plist")).is_ok());
+        assert!(refuse_protected(&item("hook|LoginHook|/Library/Preferences/x.plist")).is_ok());
+    }
+
+    #[test]
+    fn reads_emond_rules() {
+        let rules = plist::parse(
+            b"<plist><array><dict>\
+                <key>name</key><string>persist</string>\
+                <key>enabled</key><true/>\
+                <key>actions</key><array><dict>\
+                  <key>type</key><string>RunCommand</string>\
+                  <key>command</key><string>/bin/sh</string>\
+                  <key>arguments</key><array><string>-c</string><string>echo hi</string></array>\
+                </dict></array>\
+              </dict><dict>\
+                <key>name</key><string>off</string>\
+                <key>enabled</key><false/>\
+              </dict></array></plist>",
+        )
+        .unwrap();
+        assert_eq!(
+            emond_rules(&rules),
+            [
+                EmondRule {
+                    name: Some("persist".to_string()),
+                    enabled: true,
+                    command: Some(r#"/bin/sh -c "echo hi""#.to_string()),
+                },
+                EmondRule {
+                    name: Some("off".to_string()),
+                    enabled: false,
+                    command: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_latest_btm_version() {
+        let names = [
+            "BackgroundItems-v4.btm",
+            "BackgroundItems-v13.btm",
+            "other.btm",
+        ];
+        assert_eq!(
+            latest_btm(names.iter().map(|s| s.to_string())).as_deref(),
+            Some("BackgroundItems-v13.btm")
+        );
+    }
+
+    fn login_item(label: &str) -> StartupItem {
+        StartupItem {
+            label: label.to_string(),
+            path: None,
+            command: None,
+            scope: Scope::User,
+            location: None,
+            modified: None,
+            disabled: false,
+            triggers: vec![],
+            protected: false,
+            developer: None,
+            hidden: false,
+            kind: None,
+            item_type: StartupType::LoginItem,
+            delete_value: format!("loginitem|{}", label),
+        }
+    }
+
+    #[test]
+    fn merges_btm_records() {
+        let records = btm::parse(include_bytes!(
+            "../../tests/fixtures/BackgroundItems-v4.btm"
+        ))
+        .unwrap();
+
+        let mut logins = vec![login_item("Dropbox")];
+        merge_btm_login_items(&mut logins, &records);
+        assert_eq!(logins.len(), 2);
+        let helper = &logins[1];
+        assert_eq!(helper.label, "Example Helper");
+        assert_eq!(helper.developer.as_deref(), Some("Example Corp"));
+        assert!(helper.disabled);
+        assert!(helper.delete_value.starts_with("btm|"));
+
+        let mut plists = vec![StartupItem {
+            path: Some("/Library/LaunchAgents/com.example.agent.plist".to_string()),
+            item_type: StartupType::Plist,
+            ..login_item("com.example.agent")
+        }];
+        merge_btm_plists(&mut plists, &records);
+        assert_eq!(plists.len(), 2);
+        assert_eq!(plists[0].developer.as_deref(), Some("Example Corp"));
+        assert!(!plists[0].disabled);
+        assert_eq!(
+            plists[1].path.as_deref(),
+            Some("/Library/LaunchDaemons/com.other.daemon.plist")
+        );
+        assert_eq!(
+            plists[1].command.as_deref(),
+            Some(r#""/usr/local/bin/otherd""#)
+        );
+    }
+
+    #[test]
+    fn finds_helpers_in_app_bundles() {
+        let root = std::env::temp_dir().join(format!("bootwatch-apps-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let app = root.join("Utilities/Example.app");
+        let helper = app.join("Contents/Library/LoginItems/Example Launcher.app");
+        fs::create_dir_all(helper.join("Contents")).unwrap();
+        fs::write(
+            helper.join("Contents/Info.plist"),
+            "<plist><dict><key>CFBundleIdentifier</key><string>com.example.launcher</string>\
+             <key>CFBundleExecutable</key><string>launcher</string></dict></plist>",
+        )
+        .unwrap();
+        let agents = app.join("Contents/Library/LaunchAgents");
+        fs::create_dir_all(&agents).unwrap();
+        fs::write(
+            agents.join("com.example.agent.plist"),
+            "<plist><dict><key>Label</key><string>com.example.agent</string>\
+             <key>BundleProgram</key><string>Contents/MacOS/agent</string>\
+             <key>RunAtLoad</key><true/></dict></plist>",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("Plain.app")).unwrap();
+
+        let apps = app_bundles(&root);
+        assert_eq!(apps, [root.join("Plain.app"), app.clone()]);
+        assert!(bundle_helpers(&apps[0], &BTreeSet::new(), &[]).is_empty());
+
+        let records = btm::parse(include_bytes!(
+            "../../tests/fixtures/BackgroundItems-v4.btm"
+        ))
+        .unwrap();
+        let disabled = BTreeSet::from(["com.example.launcher".to_string()]);
+        let items = bundle_helpers(&app, &disabled, &records);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].label, "Example Launcher");
+        assert!(items[0].disabled);
+        assert_eq!(
+            items[0].command,
+            Some(format!(
+                "\"{}\"",
+                helper.join("Contents/MacOS/launcher").display()
+            ))
+        );
+        assert_eq!(
+            items[0].location.as_deref(),
+            Some(app.display().to_string().as_str())
+        );
+        let agent = &items[1];
+        assert_eq!(agent.label, "com.example.agent");
+        assert_eq!(
+            agent.command,
+            Some(format!(
+                "\"{}\"",
+                app.join("Contents/MacOS/agent").display()
+            ))
+        );
+        assert_eq!(agent.triggers, [Trigger::AtLoad]);
+        // 与 BTM 中的 `16.com.example.agent` 对应
+        assert_eq!(agent.developer.as_deref(), Some("Example Corp"));
+        assert!(agent.delete_value.starts_with("bundle|"));
+        assert!(refuse_protected(&OptionItem {
+            label: agent.label.clone(),
+            value: agent.delete_value.clone(),
+        })
+        .is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn parses_login_items_with_separators() {
+        let output = "Foo, Inc.\u{1f}/Applications/Foo, Inc.app/\u{1f}false\u{1f}应用程序\u{1e}\
+                      Notes\u{1f}missing value\u{1f}true\u{1f}\u{1e}\n";
+        let records = parse_login_items(output);
+        assert_eq!(
+            records,
+            [
+                LoginItemRecord {
+                    name: "Foo, Inc.".to_string(),
+                    path: Some("/Applications/Foo, Inc.app/".to_string()),
+                    hidden: false,
+                    kind: Some("应用程序".to_string()),
+                },
+                LoginItemRecord {
+                    name: "Notes".to_string(),
+                    path: None,
+                    hidden: true,
+                    kind: None,
+                },
+            ]
+        );
+        assert!(parse_login_items("\n").is_empty());
+    }
+
+    #[test]
+    fn login_items_delete_by_path() {
+        let item = |path: &str| {
+            LoginItemRecord {
+                name: "Helper".to_string(),
+                path: Some(path.to_string()),
+                hidden: false,
+                kind: None,
+            }
+            .into_item()
+        };
+        let mut items = vec![
+            item("/Applications/A/Helper.app"),
+            item("/Applications/B/Helper.app"),
+        ];
+        assert_eq!(
+            items[0].delete_value,
+            "loginitem|/Applications/A/Helper.app"
+        );
+        assert_eq!(items[1].location.as_deref(), Some("/Applications/B"));
+        assert_eq!(
+            items[1].command.as_deref(),
+            Some(r#""/Applications/B/Helper.app""#)
+        );
+
+        // 同名的 BTM 条目按路径对应，不会并入已有的同名项
+        let records = btm::parse(include_bytes!(
+            "../../tests/fixtures/BackgroundItems-v4.btm"
+        ))
+        .unwrap();
+        items[0].label = "Example Helper".to_string();
+        merge_btm_login_items(&mut items, &records);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].developer, None);
+        assert_eq!(
+            items[2].path.as_deref(),
+            Some("/Applications/Example Helper.app")
+        );
+    }
+
+    #[test]
+    fn reads_login_hooks() {
+        let prefs = plist::parse(
+            b"<plist><dict><key>LoginHook</key><string>/usr/local/bin/hook.sh</string>\
+              <key>LogoutHook</key><string> </string></dict></plist>",
+        )
+        .unwrap();
+        assert_eq!(
+            login_hooks(&prefs),
+            [("LoginHook", "/usr/local/bin/hook.sh".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_print_disabled_output() {
+        let output = "disabled services = {\n\
+            \t\"com.apple.ftp-proxy\" => disabled\n\
+            \t\"com.example.agent\" => enabled\n\
+            \t\"com.old.style\" => true\n\
+            }\n";
+        let disabled = parse_disabled(output);
+        assert!(disabled.contains("com.apple.ftp-proxy"));
+        assert!(disabled.contains("com.old.style"));
+        assert!(!disabled.contains("com.example.agent"));
+        assert_eq!(disabled.len(), 2);
+    }
 
     #[test]
     fn escape_quotes_and_backslashes() {
@@ -1,106 +1,523 @@
+// 不支持的平台上只编译跨平台模块（用于 CI 测试），平台相关的部分不会被用到
+#![cfg_attr(
+    not(any(target_os = "macos", target_os = "windows")),
+    allow(dead_code, unused_imports)
+)]
+
+// 文件格式解析模块在所有平台上编译，以便用 fixture 测试；只有对应平台会用到
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub mod btm;
+pub mod command;
 pub mod helper;
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub mod lnk;
 #[cfg(target_os = "macos")]
 pub mod macos;
+pub mod offline;
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub mod plist;
+pub mod process;
+pub mod quarantine;
+pub mod regf;
+pub mod tasks;
+pub mod xml;
 
 #[cfg(target_os = "windows")]
 pub mod windows;
 
-use helper::DisplayItem;
+use crate::config::SourceConfig;
+use crate::i18n::Lang;
+use command::Runner;
+use helper::{DisplayItem, Scope};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 启动项来源描述，`id` 即配置文件中 `[sources.<id>]` 的名称
+#[derive(Debug)]
+pub struct SourceInfo {
+    pub id: &'static str,
+    /// 展示名称，与该来源启动项的类型标签一致
+    pub label: &'static str,
+    /// 是否按目录扫描（决定 `extra_dirs` 是否生效）
+    pub scans_dirs: bool,
+    /// 未在配置中设置 `enabled` 时是否扫描
+    pub default_enabled: bool,
+}
+
+#[cfg(target_os = "macos")]
+pub const SOURCES: &[SourceInfo] = &[
+    SourceInfo {
+        id: "plist",
+        label: "Plist",
+        scans_dirs: true,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "login_item",
+        label: "Login Item",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    // Apple 自带的任务数以百计且只读，默认不扫描
+    SourceInfo {
+        id: "system_plist",
+        label: "Apple",
+        scans_dirs: false,
+        default_enabled: false,
+    },
+    SourceInfo {
+        id: "startup_items",
+        label: "StartupItems",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "login_hook",
+        label: "Login Hook",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "periodic",
+        label: "Periodic",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "rc_common",
+        label: "rc.common",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "launchd_conf",
+        label: "launchd.conf",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "emond",
+        label: "emond",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "app_bundle",
+        label: "App Bundle",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+];
+
+#[cfg(target_os = "windows")]
+pub const SOURCES: &[SourceInfo] = &[
+    SourceInfo {
+        id: "registry",
+        label: "Registry",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "startup_folder",
+        label: "StartupFolder",
+        scans_dirs: true,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "scheduled_task",
+        label: "Scheduled Task",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+];
+
+/// 其它平台（如 Linux）没有可扫描的来源；此时仍可编译，以便在 CI 中运行跨平台模块的测试
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub const SOURCES: &[SourceInfo] = &[];
+
+/// 不支持的平台上各操作返回的错误
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const UNSUPPORTED: &str = "当前平台不支持该操作";
 
 // Unified StartupType for cross-platform use
+#[cfg(any(target_os = "macos", target_os = "windows"))]
 #[derive(Debug)]
 pub enum StartupType {
     #[cfg(target_os = "macos")]
     Plist, // macOS Plist-based startup item
     #[cfg(target_os = "macos")]
     LoginItem, // macOS Login Item
+    #[cfg(target_os = "macos")]
+    SystemPlist, // Apple launchd job under /System/Library (read-only)
+    #[cfg(target_os = "macos")]
+    StartupItem, // Legacy /Library/StartupItems bundle
+    #[cfg(target_os = "macos")]
+    LoginHook, // LoginHook / LogoutHook in com.apple.loginwindow
+    #[cfg(target_os = "macos")]
+    Periodic, // periodic(8) daily / weekly / monthly script
+    #[cfg(target_os = "macos")]
+    RcCommon, // /etc/rc.common
+    #[cfg(target_os = "macos")]
+    LaunchdConf, // Legacy /etc/launchd.conf
+    #[cfg(target_os = "macos")]
+    Emond, // emond rule in /etc/emond.d/rules
+    #[cfg(target_os = "macos")]
+    AppBundle, // Login item helper or launchd plist embedded in an .app
     #[cfg(target_os = "windows")]
     Registry, // Windows Registry-based startup item
     #[cfg(target_os = "windows")]
     StartupFolder, // Windows Startup folder shortcut
+    #[cfg(target_os = "windows")]
+    ScheduledTask, // Task Scheduler task with a boot or logon trigger
 }
 
 // Unified StartupItem for cross-platform use
+#[cfg(any(target_os = "macos", target_os = "windows"))]
 #[derive(Debug)]
 pub struct StartupItem {
     pub label: String,
     pub path: Option<String>,
+    pub command: Option<String>,
+    pub scope: Scope,
+    pub location: Option<String>,
+    pub modified: Option<SystemTime>,
+    pub disabled: bool,
+    /// launchd 任务的运行时机；其它启动项为空
+    pub triggers: Vec<helper::Trigger>,
+    /// 受系统保护（如 macOS SIP），只读
+    pub protected: bool,
+    /// 开发者（macOS 取自后台任务管理数据库）
+    pub developer: Option<String>,
+    /// 登录时隐藏窗口（macOS 登录项）
+    pub hidden: bool,
+    /// 打开的对象种类（macOS 登录项）；所在自启动键的简称（Windows 注册表项）
+    pub kind: Option<String>,
+    /// 任务管理器「启动」页不显示（Windows）
+    pub unlisted: bool,
+    /// 运行身份（Windows 计划任务）
+    pub principal: Option<String>,
     pub item_type: StartupType,
     /// 平台相关的删除令牌，由各平台模块在构造时填充，
     /// 删除时原样传回对应平台的 `delete_startup_item` 解析。
     pub delete_value: String,
 }
 
+/// 扫描单个来源（跨平台入口），`id` 为 `SOURCES` 中的来源 ID。
+/// 调用外部命令的来源通过 `runner` 执行，超时或取消时返回错误
 #[cfg(target_os = "macos")]
-pub fn get_all_startup_items() -> Vec<StartupItem> {
-    macos::get_all_startup_items()
+pub fn scan_source(
+    id: &str,
+    source: &SourceConfig,
+    runner: &Runner,
+) -> Result<Vec<DisplayItem>, String> {
+    let items = macos::scan_source(id, source, runner)?;
+    Ok(items
         .into_iter()
-        .map(|item| StartupItem {
-            label: item.label,
-            path: item.path,
-            item_type: match item.item_type {
-                macos::StartupType::Plist => StartupType::Plist,
-                macos::StartupType::LoginItem => StartupType::LoginItem,
-            },
-            delete_value: item.delete_value,
+        .map(|item| {
+            to_display(StartupItem {
+                label: item.label,
+                path: item.path,
+                command: item.command,
+                scope: item.scope,
+                location: item.location,
+                modified: item.modified,
+                disabled: item.disabled,
+                triggers: item.triggers,
+                protected: item.protected,
+                developer: item.developer,
+                hidden: item.hidden,
+                kind: item.kind,
+                unlisted: false,
+                principal: None,
+                item_type: match item.item_type {
+                    macos::StartupType::Plist => StartupType::Plist,
+                    macos::StartupType::LoginItem => StartupType::LoginItem,
+                    macos::StartupType::SystemPlist => StartupType::SystemPlist,
+                    macos::StartupType::StartupItem => StartupType::StartupItem,
+                    macos::StartupType::LoginHook => StartupType::LoginHook,
+                    macos::StartupType::Periodic => StartupType::Periodic,
+                    macos::StartupType::RcCommon => StartupType::RcCommon,
+                    macos::StartupType::LaunchdConf => StartupType::LaunchdConf,
+                    macos::StartupType::Emond => StartupType::Emond,
+                    macos::StartupType::AppBundle => StartupType::AppBundle,
+                },
+                delete_value: item.delete_value,
+            })
         })
-        .collect()
+        .collect())
 }
 
+/// 扫描单个来源（跨平台入口），`id` 为 `SOURCES` 中的来源 ID
 #[cfg(target_os = "windows")]
-pub fn get_all_startup_items() -> Vec<StartupItem> {
-    windows::get_startup_apps()
-        .into_iter()
-        .map(|item| StartupItem {
-            label: item.label,
-            path: item.path,
-            item_type: match item.item_type {
-                windows::StartupType::Registry => StartupType::Registry,
-                windows::StartupType::StartupFolder => StartupType::StartupFolder,
-            },
-            delete_value: item.delete_value,
-        })
-        .collect()
-}
-
-/// 构建带展示信息（图标/类型标签/路径）的启动项列表
-pub fn get_display_items() -> Vec<DisplayItem> {
-    get_all_startup_items()
+pub fn scan_source(
+    id: &str,
+    source: &SourceConfig,
+    runner: &Runner,
+) -> Result<Vec<DisplayItem>, String> {
+    let items = windows::scan_source(id, source, runner)?;
+    Ok(items
         .into_iter()
         .map(|item| {
-            let (icon, type_label): (&str, &str) = match &item.item_type {
-                #[cfg(target_os = "macos")]
-                StartupType::Plist => ("📝", "Plist"),
-                #[cfg(target_os = "macos")]
-                StartupType::LoginItem => ("🚀", "Login Item"),
-                #[cfg(target_os = "windows")]
-                StartupType::Registry => ("🔑", "Registry"),
-                #[cfg(target_os = "windows")]
-                StartupType::StartupFolder => ("📂", "StartupFolder"),
-            };
-            DisplayItem {
-                icon: icon.to_string(),
-                type_label: type_label.to_string(),
-                label: item.label.clone(),
-                path: item.path.clone(),
-                // option.value 携带平台相关删除令牌，删除时由各平台解析
-                option: helper::OptionItem {
-                    label: format!("{}: {}", type_label, item.label),
-                    value: item.delete_value.clone(),
+            to_display(StartupItem {
+                label: item.label,
+                path: item.path,
+                command: item.command,
+                scope: item.scope,
+                location: item.location,
+                modified: item.modified,
+                disabled: item.disabled,
+                triggers: item.triggers,
+                protected: false,
+                developer: item.developer,
+                hidden: false,
+                kind: item.kind,
+                unlisted: item.unlisted,
+                principal: item.principal,
+                item_type: match item.item_type {
+                    windows::StartupType::Registry => StartupType::Registry,
+                    windows::StartupType::StartupFolder => StartupType::StartupFolder,
+                    windows::StartupType::ScheduledTask => StartupType::ScheduledTask,
                 },
-            }
+                delete_value: item.delete_value,
+            })
         })
-        .collect()
+        .collect())
+}
+
+/// 不支持的平台：没有可扫描的来源
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn scan_source(
+    id: &str,
+    _source: &SourceConfig,
+    _runner: &Runner,
+) -> Result<Vec<DisplayItem>, String> {
+    Err(format!("{}: {}", UNSUPPORTED, id))
+}
+
+/// 补充展示信息（来源/类型标签/失效判断）
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn to_display(item: StartupItem) -> DisplayItem {
+    let (source, type_label): (&'static str, &str) = match &item.item_type {
+        #[cfg(target_os = "macos")]
+        StartupType::Plist => ("plist", "Plist"),
+        #[cfg(target_os = "macos")]
+        StartupType::LoginItem => ("login_item", "Login Item"),
+        #[cfg(target_os = "macos")]
+        StartupType::SystemPlist => ("system_plist", "Apple"),
+        #[cfg(target_os = "macos")]
+        StartupType::StartupItem => ("startup_items", "StartupItems"),
+        #[cfg(target_os = "macos")]
+        StartupType::LoginHook => ("login_hook", "Login Hook"),
+        #[cfg(target_os = "macos")]
+        StartupType::Periodic => ("periodic", "Periodic"),
+        #[cfg(target_os = "macos")]
+        StartupType::RcCommon => ("rc_common", "rc.common"),
+        #[cfg(target_os = "macos")]
+        StartupType::LaunchdConf => ("launchd_conf", "launchd.conf"),
+        #[cfg(target_os = "macos")]
+        StartupType::Emond => ("emond", "emond"),
+        #[cfg(target_os = "macos")]
+        StartupType::AppBundle => ("app_bundle", "App Bundle"),
+        #[cfg(target_os = "windows")]
+        StartupType::Registry => ("registry", "Registry"),
+        #[cfg(target_os = "windows")]
+        StartupType::StartupFolder => ("startup_folder", "StartupFolder"),
+        #[cfg(target_os = "windows")]
+        StartupType::ScheduledTask => ("scheduled_task", "Scheduled Task"),
+    };
+    // 从命令行解析出可执行文件，判断启动项是否已失效
+    let target = item
+        .command
+        .as_deref()
+        .and_then(helper::executable_of)
+        .map(|exe| helper::expand_env_vars(&exe));
+    let broken = target.as_deref().is_some_and(helper::is_missing);
+    DisplayItem {
+        source,
+        type_label: type_label.to_string(),
+        // option.value 携带平台相关删除令牌，删除时由各平台解析
+        option: helper::OptionItem {
+            label: format!("{}: {}", type_label, item.label),
+            value: item.delete_value,
+        },
+        label: item.label,
+        path: item.path,
+        command: item.command,
+        scope: item.scope,
+        location: item.location,
+        modified: item.modified,
+        target,
+        broken,
+        disabled: item.disabled,
+        running: None,
+        triggers: item.triggers,
+        protected: item.protected,
+        developer: item.developer,
+        hidden: item.hidden,
+        kind: item.kind,
+        unlisted: item.unlisted,
+        principal: item.principal,
+    }
+}
+
+/// 删除指定启动项（跨平台入口）。
+/// `quarantine` 为隔离目录时，删除前先把定义文件移入（或备份到）该目录。
+pub fn delete_item(
+    item: &helper::OptionItem,
+    quarantine: Option<&Path>,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    offline::refuse(item)?;
+    #[cfg(target_os = "macos")]
+    {
+        macos::delete_startup_item(item, quarantine, runner)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::delete_startup_item(item, quarantine, runner)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (item, quarantine, runner);
+        Err(UNSUPPORTED.into())
+    }
+}
+
+/// 禁用指定启动项（跨平台入口）：保留定义，只阻止其开机运行
+pub fn disable_item(
+    item: &helper::OptionItem,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    offline::refuse(item)?;
+    #[cfg(target_os = "macos")]
+    {
+        macos::disable_startup_item(item, runner)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::disable_startup_item(item, runner)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (item, runner);
+        Err(UNSUPPORTED.into())
+    }
+}
+
+/// 在编辑器中打开的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditTarget {
+    /// 直接编辑定义文件
+    File(PathBuf),
+    /// 注册表值导出的临时 `.reg` 文件，编辑后需确认导入（仅 Windows 产生）
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    RegExport(PathBuf),
+}
+
+impl EditTarget {
+    pub fn path(&self) -> &Path {
+        match self {
+            EditTarget::File(path) | EditTarget::RegExport(path) => path,
+        }
+    }
+}
+
+/// 启动项可在编辑器中打开的定义（跨平台入口）
+pub fn edit_target(item: &helper::OptionItem) -> Result<EditTarget, String> {
+    offline::refuse(item)?;
+    #[cfg(target_os = "macos")]
+    {
+        macos::edit_target(item)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::edit_target(item)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = item;
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+/// 校验编辑后的内容（跨平台入口），返回可展示的错误信息
+pub fn validate_edit(target: &EditTarget, runner: &Runner) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::validate_edit(target, runner)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::validate_edit(target, runner)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (target, runner);
+        Err(UNSUPPORTED.to_string())
+    }
 }
 
-/// 删除指定启动项（跨平台入口）
-pub fn delete_item(item: &helper::OptionItem) -> Result<(), Box<dyn std::error::Error>> {
+/// 启动项定义中声明的日志文件（跨平台入口）。
+/// macOS 读取 plist 的 `StandardOutPath` / `StandardErrorPath`；Windows 的启动项没有日志声明
+pub fn log_files(item: &helper::OptionItem, runner: &Runner) -> Result<Vec<PathBuf>, String> {
+    // 离线扫描的启动项不在本机运行，没有可跟随的日志
+    if offline::refuse(item).is_err() {
+        return Ok(vec![]);
+    }
     #[cfg(target_os = "macos")]
     {
-        macos::delete_startup_item(item)
+        macos::log_files(item, runner)
     }
     #[cfg(target_os = "windows")]
     {
-        windows::delete_startup_item(item)
+        let _ = (item, runner);
+        Ok(vec![])
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (item, runner);
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+/// 导入编辑后的 `.reg` 文件（仅 Windows）
+pub fn import_reg(path: &Path, runner: &Runner) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::import_reg(path, runner)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (path, runner);
+        Err("仅 Windows 支持导入注册表".into())
+    }
+}
+
+/// 可对启动项（含批量）执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Delete,
+    Disable,
+}
+
+impl Operation {
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            Operation::Delete => lang.pick("删除", "delete"),
+            Operation::Disable => lang.pick("禁用", "disable"),
+        }
+    }
+
+    /// 对单个启动项执行操作；`quarantine` 仅对删除生效
+    pub fn apply(
+        self,
+        item: &helper::OptionItem,
+        quarantine: Option<&Path>,
+        runner: &Runner,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self {
+            Operation::Delete => delete_item(item, quarantine, runner),
+            Operation::Disable => disable_item(item, runner),
+        }
     }
 }
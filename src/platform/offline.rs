@@ -0,0 +1,475 @@
+use crate::platform::helper::{self, parse_token, DisplayItem, OptionItem, Scope};
+use crate::platform::regf::Hive;
+use crate::platform::tasks;
+use crate::platform::SourceInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 离线模式扫描的来源：挂载的 Windows 卷中的注册表 hive 与计划任务定义
+pub const SOURCES: &[SourceInfo] = &[
+    SourceInfo {
+        id: "registry",
+        label: "Registry",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+    SourceInfo {
+        id: "scheduled_task",
+        label: "Scheduled Task",
+        scans_dirs: false,
+        default_enabled: true,
+    },
+];
+
+/// 自启动键的启用状态（见 Windows 来源的 `StartupApproved`），位于 `Software` 键下
+const STARTUP_APPROVED: &str = r"Microsoft\Windows\CurrentVersion\Explorer\StartupApproved";
+
+/// 挂载卷对应的盘符；其它盘符的路径无法检查是否存在
+const SYSTEM_DRIVE: &str = "C:";
+
+/// 扫描单个来源，`id` 为 `SOURCES` 中的来源 ID；`root` 为 Windows 卷的挂载点
+pub fn scan_source(root: &Path, id: &str) -> Result<Vec<DisplayItem>, String> {
+    match id {
+        "registry" => registry_items(root),
+        "scheduled_task" => task_items(root),
+        _ => Err(format!("离线模式不支持该来源: {}", id)),
+    }
+}
+
+/// 一个待读取的 hive
+struct HiveFile {
+    path: PathBuf,
+    /// hive 内 `Software` 键的路径（`SOFTWARE` hive 本身即该键）
+    prefix: &'static str,
+    /// 展示用的根键，与在线扫描的键路径一致
+    display_root: String,
+    scope: Scope,
+    /// 用户配置文件名，用于展开 `%USERPROFILE%` 等变量
+    profile: Option<String>,
+}
+
+/// `SOFTWARE` 中的全系统启动项与 `Users\*\NTUSER.DAT` 中各用户的启动项
+fn registry_items(root: &Path) -> Result<Vec<DisplayItem>, String> {
+    let mut hives = vec![];
+    if let Some(path) = find_path(root, r"Windows\System32\config\SOFTWARE") {
+        hives.push(HiveFile {
+            path,
+            prefix: "",
+            display_root: r"HKEY_LOCAL_MACHINE\Software".to_string(),
+            scope: Scope::System,
+            profile: None,
+        });
+    }
+    let mut profiles: Vec<PathBuf> = find_path(root, "Users")
+        .and_then(|users| fs::read_dir(users).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    profiles.sort();
+    for dir in profiles {
+        let (Some(path), Some(profile)) = (
+            find_path(&dir, "NTUSER.DAT"),
+            dir.file_name().and_then(|n| n.to_str()),
+        ) else {
+            continue;
+        };
+        hives.push(HiveFile {
+            path,
+            prefix: "Software",
+            display_root: format!(r"HKEY_USERS\{}\Software", profile),
+            scope: Scope::User,
+            profile: Some(profile.to_string()),
+        });
+    }
+    if hives.is_empty() {
+        return Err(format!(
+            r"{} 下没有找到注册表 hive（Windows\System32\config\SOFTWARE 或 Users\*\NTUSER.DAT）",
+            root.display()
+        ));
+    }
+
+    let mut items = vec![];
+    let mut errors = vec![];
+    for file in &hives {
+        match read_hive(root, file) {
+            Ok(found) => items.extend(found),
+            Err(e) => errors.push(format!("{}: {}", file.path.display(), e)),
+        }
+    }
+    // 部分 hive 无法读取（如正被占用、已损坏）时仍展示其余结果
+    if items.is_empty() && !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+    Ok(items)
+}
+
+fn read_hive(root: &Path, file: &HiveFile) -> Result<Vec<DisplayItem>, String> {
+    let bytes = fs::read(&file.path).map_err(|e| e.to_string())?;
+    let hive = Hive::parse(bytes)?;
+    let join = |key: &str| {
+        if file.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!(r"{}\{}", file.prefix, key)
+        }
+    };
+    let hive_path = file.path.display().to_string();
+    let mut items = vec![];
+    for run_key in helper::RUN_KEYS {
+        let Some(key) = hive.open(&join(run_key.path)) else {
+            continue;
+        };
+        let approved = run_key
+            .approved
+            .and_then(|subkey| hive.open(&join(&format!(r"{}\{}", STARTUP_APPROVED, subkey))));
+        let location = format!(r"{}\{}", file.display_root, run_key.path);
+        // RunOnceEx 的命令位于编号子键中，每个子键的默认值为标题
+        let keys = if run_key.nested {
+            key.subkeys()
+                .into_iter()
+                .map(|sub| (format!(r"{}\{}", location, sub.name()), sub))
+                .collect()
+        } else {
+            vec![(location, key)]
+        };
+        for (location, key) in keys {
+            for value in key.values() {
+                if run_key.nested && value.name.is_empty() {
+                    continue;
+                }
+                let Some(command) = value.string() else {
+                    continue;
+                };
+                let (target, broken) = target_of(root, &command, file.profile.as_deref());
+                let disabled = approved
+                    .and_then(|key| key.value(&value.name))
+                    .is_some_and(|v| v.data.first().is_some_and(|b| b & 1 == 1));
+                items.push(DisplayItem {
+                    source: "registry",
+                    type_label: "Registry".to_string(),
+                    option: OptionItem {
+                        label: format!("Registry: {}", value.name),
+                        value: format!("offline|{}|{}|{}", hive_path, location, value.name),
+                    },
+                    label: value.name,
+                    path: Some(command.clone()),
+                    command: Some(command),
+                    scope: file.scope,
+                    location: Some(location.clone()),
+                    modified: key.modified(),
+                    target,
+                    broken,
+                    disabled,
+                    protected: false,
+                    developer: None,
+                    hidden: false,
+                    kind: Some(run_key.name.to_string()),
+                    unlisted: run_key.approved.is_none(),
+                    principal: None,
+                    running: None,
+                    triggers: vec![],
+                });
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// `Windows\System32\Tasks` 中有开机或登录触发器的计划任务
+fn task_items(root: &Path) -> Result<Vec<DisplayItem>, String> {
+    let dir = find_path(root, r"Windows\System32\Tasks").ok_or_else(|| {
+        format!(
+            r"{} 下没有找到计划任务目录（Windows\System32\Tasks）",
+            root.display()
+        )
+    })?;
+    Ok(tasks::scan(&dir)?
+        .into_iter()
+        .map(|task| {
+            let label = task.name().to_string();
+            let location = task.folder().to_string();
+            let definition = task.definition;
+            let (target, broken) = match &definition.command {
+                Some(command) => target_of(root, command, None),
+                None => (None, false),
+            };
+            DisplayItem {
+                source: "scheduled_task",
+                type_label: "Scheduled Task".to_string(),
+                option: OptionItem {
+                    label: format!("Scheduled Task: {}", label),
+                    value: format!("offline|{}", task.file.display()),
+                },
+                label,
+                path: Some(definition.actions.join("; ")),
+                command: definition.command,
+                scope: Scope::System,
+                location: Some(location),
+                modified: task.modified,
+                target,
+                broken,
+                disabled: !definition.enabled,
+                protected: false,
+                developer: definition.author,
+                hidden: false,
+                kind: None,
+                unlisted: false,
+                principal: definition.principal,
+                running: None,
+                triggers: definition.triggers,
+            }
+        })
+        .collect())
+}
+
+/// 命令指向的可执行文件（按默认位置展开变量）及其是否失效；只检查挂载卷上的路径
+fn target_of(root: &Path, command: &str, profile: Option<&str>) -> (Option<String>, bool) {
+    let target = helper::executable_of(command).map(|exe| expand_vars(&exe, profile));
+    let broken = target
+        .as_deref()
+        .is_some_and(|t| is_local(t) && host_path(root, t).is_none());
+    (target, broken)
+}
+
+/// 展开常见的 Windows 环境变量；值取系统默认的安装位置，而非当前主机的环境
+fn expand_vars(s: &str, profile: Option<&str>) -> String {
+    let user_dir = profile.map(|p| format!(r"{}\Users\{}", SYSTEM_DRIVE, p));
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            break;
+        };
+        let name = &after[..end];
+        let value = match name.to_ascii_lowercase().as_str() {
+            "systemdrive" => Some(SYSTEM_DRIVE.to_string()),
+            "windir" | "systemroot" => Some(format!(r"{}\Windows", SYSTEM_DRIVE)),
+            "programfiles" | "programw6432" => Some(format!(r"{}\Program Files", SYSTEM_DRIVE)),
+            "programfiles(x86)" => Some(format!(r"{}\Program Files (x86)", SYSTEM_DRIVE)),
+            "programdata" | "allusersprofile" => Some(format!(r"{}\ProgramData", SYSTEM_DRIVE)),
+            "public" => Some(format!(r"{}\Users\Public", SYSTEM_DRIVE)),
+            "userprofile" => user_dir.clone(),
+            "appdata" => user_dir.as_ref().map(|d| format!(r"{}\AppData\Roaming", d)),
+            "localappdata" => user_dir.as_ref().map(|d| format!(r"{}\AppData\Local", d)),
+            _ => None,
+        };
+        out.push_str(&rest[..start]);
+        match value {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 位于挂载卷（系统盘）上的绝对路径
+fn is_local(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3
+        && bytes[..2].eq_ignore_ascii_case(SYSTEM_DRIVE.as_bytes())
+        && bytes[2] == b'\\'
+}
+
+/// Windows 路径（如 `C:\Windows\notepad.exe`）在挂载点下对应的文件
+fn host_path(root: &Path, path: &str) -> Option<PathBuf> {
+    find_path(root, &path[SYSTEM_DRIVE.len()..])
+}
+
+/// 在 `dir` 下按 `\` 分隔的相对路径查找，各级名称忽略大小写（NTFS 挂载通常区分大小写）
+fn find_path(dir: &Path, relative: &str) -> Option<PathBuf> {
+    relative
+        .split('\\')
+        .filter(|part| !part.is_empty())
+        .try_fold(dir.to_path_buf(), |dir, part| {
+            let exact = dir.join(part);
+            if exact.exists() {
+                return Some(exact);
+            }
+            fs::read_dir(&dir)
+                .ok()?
+                .flatten()
+                .find(|e| e.file_name().to_string_lossy().eq_ignore_ascii_case(part))
+                .map(|e| e.path())
+        })
+}
+
+/// `SYSTEM` hive 中当前控制集记录的计算机名
+pub fn computer_name(root: &Path) -> Option<String> {
+    let path = find_path(root, r"Windows\System32\config\SYSTEM")?;
+    let hive = Hive::parse(fs::read(path).ok()?).ok()?;
+    let current = hive.open("Select")?.value("Current")?.dword()?;
+    hive.open(&format!(
+        r"ControlSet{:03}\Control\ComputerName\ComputerName",
+        current
+    ))?
+    .value("ComputerName")?
+    .string()
+}
+
+/// 离线扫描的结果只读：拒绝删除、禁用与编辑
+pub fn refuse(item: &OptionItem) -> Result<(), String> {
+    match parse_token(&item.value) {
+        ("offline", _) => Err(format!("离线模式只读，不能修改: {}", item.label)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按 Windows 卷的布局放置 fixture hive；`name` 区分并行运行的测试
+    fn mount(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("bootwatch-offline-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let config = root.join("Windows/System32/config");
+        fs::create_dir_all(&config).unwrap();
+        fs::write(
+            config.join("SOFTWARE"),
+            include_bytes!("../../tests/fixtures/SOFTWARE"),
+        )
+        .unwrap();
+        fs::write(
+            config.join("SYSTEM"),
+            include_bytes!("../../tests/fixtures/SYSTEM"),
+        )
+        .unwrap();
+        fs::write(root.join("Windows/System32/SecurityHealthSystray.exe"), "").unwrap();
+        let alice = root.join("Users/alice");
+        fs::create_dir_all(&alice).unwrap();
+        fs::write(
+            alice.join("ntuser.dat"),
+            include_bytes!("../../tests/fixtures/NTUSER.DAT"),
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("Users/Public")).unwrap();
+        let tasks = root.join("Windows/System32/Tasks/Vendor");
+        fs::create_dir_all(&tasks).unwrap();
+        fs::write(
+            tasks.join("BootCheck"),
+            include_bytes!("../../tests/fixtures/tasks/BootCheck.xml"),
+        )
+        .unwrap();
+        fs::write(
+            tasks.join("Daily"),
+            include_bytes!("../../tests/fixtures/tasks/Daily.xml"),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn scans_run_keys_of_all_hives() {
+        let root = mount("registry");
+        let items = scan_source(&root, "registry").unwrap();
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "SecurityHealth",
+                "Missing",
+                "Legacy32",
+                "Cleanup",
+                "1",
+                "Policy",
+                "OneDrive",
+                "更新程序",
+                "Big"
+            ]
+        );
+
+        let health = &items[0];
+        assert_eq!(health.scope, Scope::System);
+        assert_eq!(
+            health.location.as_deref(),
+            Some(r"HKEY_LOCAL_MACHINE\Software\Microsoft\Windows\CurrentVersion\Run")
+        );
+        assert_eq!(
+            health.target.as_deref(),
+            Some(r"C:\Windows\system32\SecurityHealthSystray.exe")
+        );
+        assert!(!health.broken && !health.disabled);
+        assert!(items[1].broken);
+        assert_eq!(health.kind.as_deref(), Some("Run"));
+        assert!(!health.unlisted);
+
+        // 32 位视图的启用状态记录在 `StartupApproved\Run32`
+        let legacy = &items[2];
+        assert_eq!(legacy.kind.as_deref(), Some(r"Wow6432Node\Run"));
+        assert!(legacy.disabled && !legacy.unlisted);
+        assert_eq!(
+            legacy.location.as_deref(),
+            Some(r"HKEY_LOCAL_MACHINE\Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Run")
+        );
+        // 任务管理器不显示的位置；RunOnceEx 的命令在编号子键中
+        assert!(items[3..6].iter().all(|i| i.unlisted && !i.disabled));
+        assert_eq!(items[4].kind.as_deref(), Some("RunOnceEx"));
+        assert_eq!(
+            items[4].location.as_deref(),
+            Some(r"HKEY_LOCAL_MACHINE\Software\Microsoft\Windows\CurrentVersion\RunOnceEx\0001")
+        );
+        assert_eq!(items[5].kind.as_deref(), Some(r"Policies\Explorer\Run"));
+
+        let onedrive = &items[6];
+        assert_eq!(onedrive.scope, Scope::User);
+        assert_eq!(
+            onedrive.location.as_deref(),
+            Some(r"HKEY_USERS\alice\Software\Microsoft\Windows\CurrentVersion\Run")
+        );
+        assert!(onedrive.disabled);
+        assert!(!items[7].disabled);
+        assert_eq!(
+            items[7].target.as_deref(),
+            Some(r"C:\Program Files\Updater\update.exe")
+        );
+
+        assert_eq!(computer_name(&root).as_deref(), Some("WIN-TEST"));
+        assert!(refuse(&onedrive.option).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn scans_scheduled_tasks() {
+        let root = mount("tasks");
+        let items = scan_source(&root, "scheduled_task").unwrap();
+        assert_eq!(items.len(), 1);
+        let task = &items[0];
+        assert_eq!(task.label, "BootCheck");
+        assert_eq!(task.location.as_deref(), Some(r"\Vendor"));
+        assert_eq!(task.triggers, [helper::Trigger::Boot]);
+        assert_eq!(task.principal.as_deref(), Some("SYSTEM"));
+        assert_eq!(task.target.as_deref(), Some(r"C:\Windows\system32\cmd.exe"));
+        // 挂载卷中没有 cmd.exe
+        assert!(task.broken && task.disabled);
+        assert!(refuse(&task.option).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn missing_hives_are_reported() {
+        let root =
+            std::env::temp_dir().join(format!("bootwatch-offline-empty-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        assert!(scan_source(&root, "registry").is_err());
+        assert!(scan_source(&root, "scheduled_task").is_err());
+        assert!(computer_name(&root).is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn expands_default_locations() {
+        assert_eq!(
+            expand_vars(r"%LOCALAPPDATA%\app.exe %UNKNOWN%", Some("bob")),
+            r"C:\Users\bob\AppData\Local\app.exe %UNKNOWN%"
+        );
+        assert_eq!(expand_vars("%USERPROFILE%", None), "%USERPROFILE%");
+        assert!(is_local(r"c:\x") && !is_local(r"D:\x") && !is_local("C:"));
+    }
+}
@@ -0,0 +1,676 @@
+use crate::platform::helper::Trigger;
+use crate::platform::xml::{Reader, Tag};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+/// plist 中的值
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    /// 自 2001-01-01 00:00:00 UTC 起的秒数
+    Date(f64),
+    Data(Vec<u8>),
+    Array(Vec<Value>),
+    Dict(BTreeMap<String, Value>),
+    /// 二进制格式中的对象引用（`NSKeyedArchiver` 使用）
+    Uid(u64),
+}
+
+impl Value {
+    /// 字典中的值；不是字典或没有该键时为 `None`
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Dict(dict) => dict.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// 解析 plist，按文件头自动识别 XML 与 `bplist00` 二进制格式
+pub fn parse(bytes: &[u8]) -> Result<Value, String> {
+    if bytes.starts_with(b"bplist00") {
+        return Binary::parse(bytes);
+    }
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = std::str::from_utf8(bytes).map_err(|_| "plist 不是有效的 UTF-8".to_string())?;
+    Xml {
+        xml: Reader::new(text, "plist 格式错误"),
+    }
+    .document()
+}
+
+/// launchd 定义实际启动的命令行：`Program` 为可执行文件（缺省时取 `ProgramArguments` 的第一项），
+/// `ProgramArguments` 其余各项为参数；含空白的部分加引号，与 `helper::split_command_line` 对应
+pub fn launchd_command(definition: &Value) -> Option<String> {
+    let args: Vec<&str> = definition
+        .get("ProgramArguments")
+        .and_then(Value::as_array)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    let program = definition
+        .get("Program")
+        .and_then(Value::as_str)
+        .or(args.first().copied())?;
+    let quote = |s: &str| {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            format!("\"{}\"", s)
+        } else {
+            s.to_string()
+        }
+    };
+    let parts: Vec<String> = std::iter::once(program)
+        .chain(args.iter().skip(1).copied())
+        .map(quote)
+        .collect();
+    Some(parts.join(" "))
+}
+
+/// launchd 定义中 `StandardOutPath` / `StandardErrorPath` 指向的日志文件，相同的只保留一个
+pub fn launchd_log_paths(definition: &Value) -> Vec<String> {
+    let mut paths: Vec<String> = vec![];
+    for key in ["StandardOutPath", "StandardErrorPath"] {
+        if let Some(path) = definition.get(key).and_then(Value::as_str) {
+            if !path.is_empty() && !paths.iter().any(|p| p == path) {
+                paths.push(path.to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// launchd 定义的运行时机：`RunAtLoad`、`KeepAlive`、`StartInterval`、`StartCalendarInterval`、
+/// `WatchPaths` / `QueueDirectories`；以上都没有时只在被请求时运行（`MachServices`、`Sockets` 等）
+pub fn launchd_triggers(definition: &Value) -> Vec<Trigger> {
+    let mut triggers = vec![];
+    if definition.get("RunAtLoad") == Some(&Value::Boolean(true)) {
+        triggers.push(Trigger::AtLoad);
+    }
+    // `KeepAlive` 为字典时表示按条件保持运行，launchd 同样在加载时启动它
+    match definition.get("KeepAlive") {
+        Some(Value::Boolean(true)) => triggers.push(Trigger::KeepAlive),
+        Some(Value::Dict(conditions)) if !conditions.is_empty() => {
+            triggers.push(Trigger::KeepAlive)
+        }
+        _ => {}
+    }
+    if let Some(&Value::Integer(secs @ 1..)) = definition.get("StartInterval") {
+        triggers.push(Trigger::Interval(secs as u64));
+    }
+    match definition.get("StartCalendarInterval") {
+        Some(Value::Dict(_)) => triggers.push(Trigger::Calendar),
+        Some(Value::Array(entries)) if !entries.is_empty() => triggers.push(Trigger::Calendar),
+        _ => {}
+    }
+    let watches = ["WatchPaths", "QueueDirectories"].iter().any(|key| {
+        definition
+            .get(key)
+            .and_then(Value::as_array)
+            .is_some_and(|paths| !paths.is_empty())
+    });
+    if watches {
+        triggers.push(Trigger::WatchPaths);
+    }
+    if triggers.is_empty() {
+        triggers.push(Trigger::OnDemand);
+    }
+    triggers
+}
+
+/// XML plist 的递归下降解析器，只支持 plist DTD 中的元素
+struct Xml<'a> {
+    xml: Reader<'a>,
+}
+
+impl<'a> Xml<'a> {
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        self.xml.error(message)
+    }
+
+    fn tag(&mut self) -> Result<Tag<'a>, String> {
+        self.xml.tag()
+    }
+
+    fn expect_close(&mut self, name: &str) -> Result<(), String> {
+        self.xml.expect_close(name)
+    }
+
+    /// 读取元素的文本内容直到 `</name>`
+    fn text(&mut self, name: &str) -> Result<String, String> {
+        let mut out = String::new();
+        self.xml.text(name, &mut out)?;
+        self.expect_close(name)?;
+        Ok(out)
+    }
+
+    fn document(mut self) -> Result<Value, String> {
+        let value = match self.tag()? {
+            Tag::Open("plist") => {
+                let value = self.tag().and_then(|tag| self.value(tag))?;
+                self.expect_close("plist")?;
+                value
+            }
+            tag => self.value(tag)?,
+        };
+        self.xml.end()?;
+        Ok(value)
+    }
+
+    fn value(&mut self, tag: Tag<'a>) -> Result<Value, String> {
+        match tag {
+            Tag::Open("dict") => {
+                let mut dict = BTreeMap::new();
+                loop {
+                    match self.tag()? {
+                        Tag::Close("dict") => return Ok(Value::Dict(dict)),
+                        Tag::Open("key") => {
+                            let key = self.text("key")?;
+                            let value = self.tag().and_then(|tag| self.value(tag))?;
+                            dict.insert(key, value);
+                        }
+                        Tag::Empty("key") => {
+                            let value = self.tag().and_then(|tag| self.value(tag))?;
+                            dict.insert(String::new(), value);
+                        }
+                        other => {
+                            return Err(self.error(format!("此处应为 <key>，实际为 {:?}", other)))
+                        }
+                    }
+                }
+            }
+            Tag::Open("array") => {
+                let mut items = vec![];
+                loop {
+                    match self.tag()? {
+                        Tag::Close("array") => return Ok(Value::Array(items)),
+                        tag => items.push(self.value(tag)?),
+                    }
+                }
+            }
+            Tag::Empty("dict") => Ok(Value::Dict(BTreeMap::new())),
+            Tag::Empty("array") => Ok(Value::Array(vec![])),
+            Tag::Empty("string") => Ok(Value::String(String::new())),
+            Tag::Empty("data") => Ok(Value::Data(vec![])),
+            Tag::Empty("true") => Ok(Value::Boolean(true)),
+            Tag::Empty("false") => Ok(Value::Boolean(false)),
+            Tag::Open(name @ ("true" | "false")) => {
+                self.expect_close(name)?;
+                Ok(Value::Boolean(name == "true"))
+            }
+            Tag::Open("string") => self.text("string").map(Value::String),
+            Tag::Open("integer") => {
+                let text = self.text("integer")?;
+                let text = text.trim();
+                let parsed = match text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+                    Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                    None => text.parse().ok(),
+                };
+                parsed
+                    .map(Value::Integer)
+                    .ok_or_else(|| self.error(format!("无效的整数: {}", text)))
+            }
+            Tag::Open("real") => {
+                let text = self.text("real")?;
+                text.trim()
+                    .parse()
+                    .map(Value::Real)
+                    .map_err(|_| self.error(format!("无效的实数: {}", text.trim())))
+            }
+            Tag::Open("date") => {
+                let text = self.text("date")?;
+                parse_date(text.trim())
+                    .map(Value::Date)
+                    .ok_or_else(|| self.error(format!("无效的日期: {}", text.trim())))
+            }
+            Tag::Open("data") => {
+                let text = self.text("data")?;
+                decode_base64(&text)
+                    .map(Value::Data)
+                    .ok_or_else(|| self.error("无效的 base64 数据"))
+            }
+            other => Err(self.error(format!("不支持的元素 {:?}", other))),
+        }
+    }
+}
+
+/// 2001-01-01 与 1970-01-01 相差的秒数
+const APPLE_EPOCH_OFFSET: i64 = 978_307_200;
+
+/// 解析 `YYYY-MM-DDTHH:MM:SSZ`，返回自 2001-01-01 起的秒数
+fn parse_date(text: &str) -> Option<f64> {
+    let text = text.strip_suffix('Z')?;
+    let (date, time) = text.split_once('T')?;
+    let mut date = date.split('-').map(|s| s.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.split(':').map(|s| s.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // 公历日期转为自 1970-01-01 起的天数（Howard Hinnant 的 days_from_civil）
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let unix = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    Some((unix - APPLE_EPOCH_OFFSET) as f64)
+}
+
+/// 解码 base64（忽略空白），无效时返回 `None`
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut out = vec![];
+    let mut buf = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        buf = (buf << 6) | u32::from(v);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// `bplist00` 二进制 plist：文件末尾 32 字节的 trailer 给出偏移表位置，
+/// 对象之间以序号相互引用
+struct Binary<'a> {
+    data: &'a [u8],
+    offsets: Vec<usize>,
+    ref_size: usize,
+    /// 还可以解码的对象数
+    budget: Cell<usize>,
+}
+
+/// 对象嵌套的最大深度，防止循环引用导致无限递归
+const MAX_DEPTH: usize = 128;
+/// 每个对象平均可被解码的次数；共享引用层层叠加时解码量会指数增长，超出即视为损坏
+const MAX_DECODES_PER_OBJECT: usize = 16;
+
+impl<'a> Binary<'a> {
+    fn parse(data: &'a [u8]) -> Result<Value, String> {
+        const ERR: &str = "二进制 plist 已损坏";
+        if data.len() < 8 + 32 {
+            return Err(format!("{}: 文件过短", ERR));
+        }
+        let trailer = &data[data.len() - 32..];
+        let offset_size = trailer[6] as usize;
+        let ref_size = trailer[7] as usize;
+        let count = read_uint(&trailer[8..16]) as usize;
+        let top = read_uint(&trailer[16..24]) as usize;
+        let table = read_uint(&trailer[24..32]) as usize;
+        if !(1..=8).contains(&offset_size) || !(1..=8).contains(&ref_size) {
+            return Err(format!("{}: 无效的偏移宽度", ERR));
+        }
+        let table_end = count
+            .checked_mul(offset_size)
+            .and_then(|len| len.checked_add(table))
+            .filter(|&end| end <= data.len() - 32)
+            .ok_or_else(|| format!("{}: 偏移表越界", ERR))?;
+        let offsets = data[table..table_end]
+            .chunks(offset_size)
+            .map(|chunk| read_uint(chunk) as usize)
+            .collect();
+        let binary = Binary {
+            data,
+            // 留出 MAX_DEPTH 的余量，循环引用先由深度限制报告
+            budget: Cell::new(
+                count
+                    .saturating_mul(MAX_DECODES_PER_OBJECT)
+                    .saturating_add(MAX_DEPTH),
+            ),
+            offsets,
+            ref_size,
+        };
+        binary.object(top, 0)
+    }
+
+    fn slice(&self, start: usize, len: usize) -> Result<&'a [u8], String> {
+        start
+            .checked_add(len)
+            .and_then(|end| self.data.get(start..end))
+            .ok_or_else(|| "二进制 plist 已损坏: 对象越界".to_string())
+    }
+
+    /// `offset` 之后第 `n` 个字节的位置
+    fn after(offset: usize, n: usize) -> Result<usize, String> {
+        offset
+            .checked_add(n)
+            .ok_or_else(|| "二进制 plist 已损坏: 对象越界".to_string())
+    }
+
+    /// 对象的元素个数与内容起点：标记低 4 位为 0xF 时，个数由紧随其后的整数对象给出
+    fn length(&self, offset: usize, low: u8) -> Result<(usize, usize), String> {
+        if low != 0xF {
+            return Ok((low as usize, Self::after(offset, 1)?));
+        }
+        let marker = self.slice(Self::after(offset, 1)?, 1)?[0];
+        if marker >> 4 != 0x1 {
+            return Err("二进制 plist 已损坏: 无效的长度".to_string());
+        }
+        let size = 1usize << (marker & 0xF);
+        let start = Self::after(offset, 2)?;
+        let len = read_uint(self.slice(start, size.min(8))?) as usize;
+        Ok((len, Self::after(start, size)?))
+    }
+
+    fn refs(&self, start: usize, count: usize) -> Result<Vec<usize>, String> {
+        let len = count
+            .checked_mul(self.ref_size)
+            .ok_or_else(|| "二进制 plist 已损坏: 引用越界".to_string())?;
+        Ok(self
+            .slice(start, len)?
+            .chunks(self.ref_size)
+            .map(|chunk| read_uint(chunk) as usize)
+            .collect())
+    }
+
+    fn object(&self, index: usize, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err("二进制 plist 嵌套过深（可能存在循环引用）".to_string());
+        }
+        let budget = self.budget.get();
+        if budget == 0 {
+            return Err("二进制 plist 中共享引用过多".to_string());
+        }
+        self.budget.set(budget - 1);
+        let offset = *self
+            .offsets
+            .get(index)
+            .ok_or_else(|| format!("二进制 plist 已损坏: 对象 {} 不存在", index))?;
+        let marker = self.slice(offset, 1)?[0];
+        let (high, low) = (marker >> 4, marker & 0xF);
+        match high {
+            0x0 => match low {
+                0x8 => Ok(Value::Boolean(false)),
+                0x9 => Ok(Value::Boolean(true)),
+                _ => Err(format!("二进制 plist 中不支持的对象类型 0x{:02x}", marker)),
+            },
+            0x1 => {
+                let size = 1usize << low;
+                let bytes = self.slice(Self::after(offset, 1)?, size)?;
+                // 8 字节为有符号数；16 字节整数只取低 8 字节
+                let bytes = &bytes[size.saturating_sub(8)..];
+                Ok(Value::Integer(read_uint(bytes) as i64))
+            }
+            0x2 => {
+                let bytes = self.slice(Self::after(offset, 1)?, 1usize << low)?;
+                match bytes.len() {
+                    4 => Ok(Value::Real(f64::from(f32::from_bits(
+                        read_uint(bytes) as u32
+                    )))),
+                    8 => Ok(Value::Real(f64::from_bits(read_uint(bytes)))),
+                    n => Err(format!("二进制 plist 中无效的实数长度 {}", n)),
+                }
+            }
+            0x3 if low == 0x3 => {
+                let bytes = self.slice(Self::after(offset, 1)?, 8)?;
+                Ok(Value::Date(f64::from_bits(read_uint(bytes))))
+            }
+            0x4 => {
+                let (len, start) = self.length(offset, low)?;
+                Ok(Value::Data(self.slice(start, len)?.to_vec()))
+            }
+            0x5 => {
+                let (len, start) = self.length(offset, low)?;
+                let bytes = self.slice(start, len)?;
+                Ok(Value::String(String::from_utf8_lossy(bytes).into_owned()))
+            }
+            0x6 => {
+                let (len, start) = self.length(offset, low)?;
+                let bytes = self.slice(start, len.saturating_mul(2))?;
+                let units: Vec<u16> = bytes
+                    .chunks(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                Ok(Value::String(String::from_utf16_lossy(&units)))
+            }
+            0x8 => Ok(Value::Uid(read_uint(
+                self.slice(Self::after(offset, 1)?, low as usize + 1)?,
+            ))),
+            0xA => {
+                let (len, start) = self.length(offset, low)?;
+                self.refs(start, len)?
+                    .into_iter()
+                    .map(|i| self.object(i, depth + 1))
+                    .collect::<Result<_, _>>()
+                    .map(Value::Array)
+            }
+            0xD => {
+                let (len, start) = self.length(offset, low)?;
+                let keys = self.refs(start, len)?;
+                let values = len
+                    .checked_mul(self.ref_size)
+                    .and_then(|keys| start.checked_add(keys))
+                    .ok_or_else(|| "二进制 plist 已损坏: 引用越界".to_string())
+                    .and_then(|values| self.refs(values, len))?;
+                let mut dict = BTreeMap::new();
+                for (k, v) in keys.into_iter().zip(values) {
+                    let Value::String(key) = self.object(k, depth + 1)? else {
+                        return Err("二进制 plist 中字典的键不是字符串".to_string());
+                    };
+                    dict.insert(key, self.object(v, depth + 1)?);
+                }
+                Ok(Value::Dict(dict))
+            }
+            _ => Err(format!("二进制 plist 中不支持的对象类型 0x{:02x}", marker)),
+        }
+    }
+}
+
+/// 大端无符号整数（不超过 8 字节）
+fn read_uint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, &b| (n << 8) | u64::from(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML_FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/agent.plist");
+    const BINARY_FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/agent.bplist");
+
+    fn check_agent(value: &Value) {
+        assert_eq!(
+            value.get("Label").and_then(Value::as_str),
+            Some("com.example.agent")
+        );
+        assert_eq!(value.get("RunAtLoad"), Some(&Value::Boolean(true)));
+        assert_eq!(value.get("StartInterval"), Some(&Value::Integer(3600)));
+        assert_eq!(value.get("Nice"), Some(&Value::Integer(-5)));
+        assert_eq!(value.get("Weight"), Some(&Value::Real(0.5)));
+        assert_eq!(
+            value.get("Comment").and_then(Value::as_str),
+            Some("日志 <A & B> ✓")
+        );
+        assert_eq!(value.get("Blob"), Some(&Value::Data(b"hello".to_vec())));
+        // 2024-01-02T03:04:05Z
+        assert_eq!(value.get("Since"), Some(&Value::Date(725_857_445.0)));
+        assert_eq!(
+            value.get("KeepAlive").and_then(|k| k.get("SuccessfulExit")),
+            Some(&Value::Boolean(false))
+        );
+        assert_eq!(
+            launchd_command(value).as_deref(),
+            Some(r#""/Applications/Example App.app/Contents/MacOS/agent" --daemon -v"#)
+        );
+        assert_eq!(
+            launchd_log_paths(value),
+            ["/tmp/example.out.log", "/tmp/example.err.log"]
+        );
+    }
+
+    #[test]
+    fn parses_xml_fixture() {
+        check_agent(&parse(XML_FIXTURE).unwrap());
+    }
+
+    #[test]
+    fn parses_binary_fixture() {
+        check_agent(&parse(BINARY_FIXTURE).unwrap());
+    }
+
+    #[test]
+    fn xml_handles_cdata_comments_and_empty_elements() {
+        let text = r#"<?xml version="1.0"?>
+            <plist version="1.0"><dict>
+              <!-- 注释 -->
+              <key>A</key><string><![CDATA[<raw> & text]]></string>
+              <key>B</key><string/>
+              <key>C</key><array/>
+              <key>D</key><integer>0x1F</integer>
+              <key>E</key><string>&#x41;&#66;</string>
+            </dict></plist>"#;
+        let value = parse(text.as_bytes()).unwrap();
+        assert_eq!(value.get("A").and_then(Value::as_str), Some("<raw> & text"));
+        assert_eq!(value.get("B").and_then(Value::as_str), Some(""));
+        assert_eq!(value.get("C"), Some(&Value::Array(vec![])));
+        assert_eq!(value.get("D"), Some(&Value::Integer(31)));
+        assert_eq!(value.get("E").and_then(Value::as_str), Some("AB"));
+    }
+
+    #[test]
+    fn xml_errors_report_line() {
+        let err = parse(b"<plist>\n<dict>\n<key>A</key>\n<integer>x</integer>\n</dict></plist>")
+            .unwrap_err();
+        assert!(err.contains("第 4 行"), "{}", err);
+        assert!(parse(b"<plist><dict><key>A</key></plist>").is_err());
+        assert!(parse(b"<plist><string>&bogus;</string></plist>").is_err());
+    }
+
+    #[test]
+    fn program_key_overrides_first_argument() {
+        let mut dict = BTreeMap::new();
+        dict.insert(
+            "Program".to_string(),
+            Value::String("/bin/real".to_string()),
+        );
+        dict.insert(
+            "ProgramArguments".to_string(),
+            Value::Array(vec![
+                Value::String("argv0".to_string()),
+                Value::String("two words".to_string()),
+            ]),
+        );
+        assert_eq!(
+            launchd_command(&Value::Dict(dict)).as_deref(),
+            Some(r#"/bin/real "two words""#)
+        );
+        assert_eq!(launchd_command(&Value::Dict(BTreeMap::new())), None);
+    }
+
+    #[test]
+    fn classifies_launchd_triggers() {
+        let definition =
+            |xml: &str| parse(format!("<plist><dict>{}</dict></plist>", xml).as_bytes()).unwrap();
+        assert_eq!(
+            launchd_triggers(&parse(XML_FIXTURE).unwrap()),
+            [Trigger::AtLoad, Trigger::KeepAlive, Trigger::Interval(3600)]
+        );
+        assert_eq!(
+            launchd_triggers(&definition(
+                "<key>KeepAlive</key><false/><key>StartCalendarInterval</key><dict><key>Hour</key><integer>3</integer></dict>"
+            )),
+            [Trigger::Calendar]
+        );
+        assert_eq!(
+            launchd_triggers(&definition(
+                "<key>WatchPaths</key><array><string>/etc/hosts</string></array>"
+            )),
+            [Trigger::WatchPaths]
+        );
+        assert_eq!(
+            launchd_triggers(&definition(
+                "<key>MachServices</key><dict><key>com.example.xpc</key><true/></dict>"
+            )),
+            [Trigger::OnDemand]
+        );
+    }
+
+    #[test]
+    fn binary_rejects_truncated_and_cyclic_input() {
+        assert!(parse(&BINARY_FIXTURE[..BINARY_FIXTURE.len() - 10]).is_err());
+        // 唯一的对象是引用自身的单元素数组
+        let mut data = b"bplist00".to_vec();
+        data.extend([0xA1, 0x00]);
+        data.push(8); // 偏移表：对象 0 位于偏移 8
+        let mut trailer = [0u8; 32];
+        trailer[6] = 1;
+        trailer[7] = 1;
+        trailer[15] = 1; // 对象数
+        trailer[31] = 10; // 偏移表位置
+        data.extend(trailer);
+        let err = parse(&data).unwrap_err();
+        assert!(err.contains("循环"), "{}", err);
+    }
+
+    #[test]
+    fn binary_rejects_exponential_sharing() {
+        // 对象 i 为数组 [i+1, i+1]，共 60 层，最后一个对象为 true
+        const LEVELS: usize = 60;
+        let mut data = b"bplist00".to_vec();
+        let mut offsets = vec![];
+        for i in 0..LEVELS {
+            offsets.push(data.len() as u8);
+            data.extend([0xA2, i as u8 + 1, i as u8 + 1]);
+        }
+        offsets.push(data.len() as u8);
+        data.push(0x09);
+        let table = data.len();
+        data.extend(&offsets);
+        let mut trailer = [0u8; 32];
+        trailer[6] = 1;
+        trailer[7] = 1;
+        trailer[15] = offsets.len() as u8;
+        trailer[31] = table as u8;
+        data.extend(trailer);
+        let err = parse(&data).unwrap_err();
+        assert!(err.contains("共享引用"), "{}", err);
+    }
+
+    #[test]
+    fn binary_rejects_huge_lengths() {
+        // 字典的元素个数为 usize::MAX
+        let mut data = b"bplist00".to_vec();
+        data.extend([0xDF, 0x13]);
+        data.extend([0xFF; 8]);
+        data.push(8);
+        let mut trailer = [0u8; 32];
+        trailer[6] = 1;
+        trailer[7] = 1;
+        trailer[15] = 1;
+        trailer[31] = 18;
+        data.extend(trailer);
+        assert!(parse(&data).is_err());
+    }
+}
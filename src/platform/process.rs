@@ -0,0 +1,445 @@
+use crate::platform::command::Runner;
+use std::process::Command;
+use std::time::Duration;
+
+/// 运行中的进程
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// 可执行文件的完整路径；无权限读取时为 `None`
+    pub exe: Option<String>,
+    /// 命令行（含 argv[0]）；无法读取时为空，Windows 不读取命令行
+    pub argv: Vec<String>,
+    /// 累计占用的 CPU 时间
+    pub cpu_time: Option<Duration>,
+    /// 常驻内存（字节）
+    pub memory: Option<u64>,
+}
+
+/// 在进程快照中查找启动项对应的进程：可执行文件须一致（Windows 不区分大小写），
+/// 只有读不到可执行文件路径时才退而比较可被进程自行修改的 argv[0]；
+/// 启动项带参数且进程命令行可读时，参数也须一致
+pub fn find<'a>(
+    processes: &'a [ProcessInfo],
+    target: &str,
+    args: &[String],
+) -> Option<&'a ProcessInfo> {
+    processes.iter().find(|p| {
+        let exe_matches = match &p.exe {
+            Some(exe) => same_path(exe, target),
+            None => p.argv.first().is_some_and(|argv0| same_path(argv0, target)),
+        };
+        let args_match = args.is_empty() || p.argv.is_empty() || p.argv[1..] == *args;
+        exe_matches && args_match
+    })
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    if cfg!(windows) {
+        a.replace('/', "\\")
+            .eq_ignore_ascii_case(&b.replace('/', "\\"))
+    } else {
+        a == b
+    }
+}
+
+/// 结束进程：Windows 用 `taskkill /T /F`（连同子进程），其余平台发送 `SIGTERM`
+pub fn terminate(pid: u32, runner: &Runner) -> Result<(), String> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("taskkill");
+        command.args(["/PID", &pid.to_string(), "/T", "/F"]);
+        command
+    } else {
+        let mut command = Command::new("kill");
+        command.arg(pid.to_string());
+        command
+    };
+    let output = runner
+        .output(&mut command)
+        .map_err(|e| format!("无法结束进程 {}: {}", pid, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let err = String::from_utf8_lossy(&output.stderr);
+        Err(format!("无法结束进程 {}: {}", pid, err.trim()))
+    }
+}
+
+/// 内存大小的可读形式，如 `34.5 MB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// CPU 时间的可读形式，如 `3.2s`、`1m02s`、`2h05m`
+pub fn format_cpu_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", time.as_secs_f64())
+    } else if secs < 3_600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3_600, secs % 3_600 / 60)
+    }
+}
+
+/// 当前全部进程的快照（`/proc`）
+#[cfg(target_os = "linux")]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .map(|pid| {
+            let dir = format!("/proc/{}", pid);
+            let stat = fs::read_to_string(format!("{}/stat", dir)).ok();
+            ProcessInfo {
+                pid,
+                exe: fs::read_link(format!("{}/exe", dir))
+                    .ok()
+                    .map(|p| p.display().to_string()),
+                argv: fs::read(format!("{}/cmdline", dir))
+                    .map(|bytes| split_nul(&bytes))
+                    .unwrap_or_default(),
+                cpu_time: stat.as_deref().and_then(parse_stat_cpu_time),
+                // statm 第二列为常驻页数
+                memory: fs::read_to_string(format!("{}/statm", dir))
+                    .ok()
+                    .and_then(|s| s.split_whitespace().nth(1)?.parse::<u64>().ok())
+                    .map(|pages| pages * 4096),
+            }
+        })
+        .collect()
+}
+
+/// `/proc/<pid>/stat` 中的 utime + stime（第 14、15 列，单位为 1/100 秒）。
+/// 第 2 列进程名可含空格与括号，因此从最后一个 `)` 之后开始计数
+#[cfg(any(target_os = "linux", test))]
+fn parse_stat_cpu_time(stat: &str) -> Option<Duration> {
+    const TICKS_PER_SEC: u64 = 100;
+    let rest = &stat[stat.rfind(')')? + 1..];
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(Duration::from_millis(
+        (utime + stime) * 1000 / TICKS_PER_SEC,
+    ))
+}
+
+/// 以 NUL 分隔的字符串列表（`/proc/<pid>/cmdline`、`KERN_PROCARGS2` 的参数区）
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn split_nul(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .split(|&b| b == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| String::from_utf8_lossy(s).into_owned())
+        .collect()
+}
+
+/// 当前全部进程的快照（`proc_listallpids` / `proc_pidpath` / `sysctl`）
+#[cfg(target_os = "macos")]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    use std::mem::size_of;
+
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 {
+        return vec![];
+    }
+    // 两次调用之间可能有新进程启动，多留一些余量
+    let mut pids = vec![0 as libc::pid_t; count as usize + 64];
+    let written = unsafe {
+        libc::proc_listallpids(
+            pids.as_mut_ptr().cast(),
+            (pids.len() * size_of::<libc::pid_t>()) as libc::c_int,
+        )
+    };
+    pids.truncate(written.max(0) as usize);
+    let timebase = mach_timebase();
+    pids.into_iter()
+        .filter(|&pid| pid > 0)
+        .map(|pid| {
+            let task = task_info(pid);
+            ProcessInfo {
+                pid: pid as u32,
+                exe: pid_path(pid),
+                argv: process_args(pid).unwrap_or_default(),
+                cpu_time: task.map(|t| {
+                    let ticks = t.pti_total_user + t.pti_total_system;
+                    Duration::from_nanos(ticks * u64::from(timebase.0) / u64::from(timebase.1))
+                }),
+                memory: task.map(|t| t.pti_resident_size),
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn pid_path(pid: libc::pid_t) -> Option<String> {
+    let mut buf = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe { libc::proc_pidpath(pid, buf.as_mut_ptr().cast(), buf.len() as u32) };
+    (len > 0).then(|| String::from_utf8_lossy(&buf[..len as usize]).into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn task_info(pid: libc::pid_t) -> Option<libc::proc_taskinfo> {
+    use std::mem::{size_of, zeroed};
+
+    let mut info: libc::proc_taskinfo = unsafe { zeroed() };
+    let size = size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDTASKINFO,
+            0,
+            (&mut info as *mut libc::proc_taskinfo).cast(),
+            size,
+        )
+    };
+    (written == size).then_some(info)
+}
+
+/// `proc_taskinfo` 中的 CPU 时间以 mach 时基为单位（Apple Silicon 上不是纳秒），返回 (numer, denom)
+#[cfg(target_os = "macos")]
+#[allow(deprecated)]
+fn mach_timebase() -> (u32, u32) {
+    let mut info = libc::mach_timebase_info { numer: 0, denom: 0 };
+    let ok = unsafe { libc::mach_timebase_info(&mut info) } == 0;
+    if ok && info.denom != 0 {
+        (info.numer, info.denom)
+    } else {
+        (1, 1)
+    }
+}
+
+/// 通过 `sysctl(KERN_PROCARGS2)` 读取命令行；其它用户的进程通常无权读取
+#[cfg(target_os = "macos")]
+fn process_args(pid: libc::pid_t) -> Option<Vec<String>> {
+    use std::mem::size_of;
+    use std::ptr::null_mut;
+
+    let mut argmax: libc::c_int = 0;
+    let mut size = size_of::<libc::c_int>();
+    let mut mib = [libc::CTL_KERN, libc::KERN_ARGMAX];
+    let ok = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            2,
+            (&mut argmax as *mut libc::c_int).cast(),
+            &mut size,
+            null_mut(),
+            0,
+        )
+    } == 0;
+    if !ok || argmax <= 0 {
+        return None;
+    }
+    let mut buf = vec![0u8; argmax as usize];
+    let mut size = buf.len();
+    let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid];
+    let ok = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            3,
+            buf.as_mut_ptr().cast(),
+            &mut size,
+            null_mut(),
+            0,
+        )
+    } == 0;
+    if !ok {
+        return None;
+    }
+    buf.truncate(size);
+    parse_procargs(&buf)
+}
+
+/// 解析 `KERN_PROCARGS2`：开头为 argc（本机字节序 i32），随后是以 NUL 结尾的可执行文件路径
+/// 与若干填充 NUL，再后是 argc 个以 NUL 结尾的参数
+#[cfg(any(target_os = "macos", test))]
+fn parse_procargs(buf: &[u8]) -> Option<Vec<String>> {
+    let argc = i32::from_ne_bytes(buf.get(..4)?.try_into().ok()?);
+    let rest = &buf[4..];
+    let exec_end = rest.iter().position(|&b| b == 0)?;
+    let args = &rest[exec_end..];
+    let start = args.iter().position(|&b| b != 0)?;
+    let mut argv = split_nul(&args[start..]);
+    argv.truncate(argc.max(0) as usize);
+    Some(argv)
+}
+
+/// 当前全部进程的快照（ToolHelp 进程快照）
+#[cfg(target_os = "windows")]
+pub fn snapshot() -> Vec<ProcessInfo> {
+    use std::mem::{size_of, zeroed};
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let mut processes = vec![];
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return processes;
+        }
+        let mut entry: PROCESSENTRY32W = zeroed();
+        entry.dwSize = size_of::<PROCESSENTRY32W>() as u32;
+        let mut ok = Process32FirstW(snapshot, &mut entry);
+        while ok != 0 {
+            processes.push(windows_process(entry.th32ProcessID));
+            ok = Process32NextW(snapshot, &mut entry);
+        }
+        CloseHandle(snapshot);
+    }
+    processes
+}
+
+/// 打开进程读取完整路径、CPU 时间与工作集；无权限时只返回 PID
+#[cfg(target_os = "windows")]
+fn windows_process(pid: u32) -> ProcessInfo {
+    use std::mem::{size_of, zeroed};
+    use windows_sys::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows_sys::Win32::System::ProcessStatus::{
+        K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let mut info = ProcessInfo {
+        pid,
+        exe: None,
+        argv: vec![],
+        cpu_time: None,
+        memory: None,
+    };
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return info;
+        }
+        let mut buf = [0u16; 32_768];
+        let mut len = buf.len() as u32;
+        if QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buf.as_mut_ptr(), &mut len) != 0 {
+            info.exe = Some(String::from_utf16_lossy(&buf[..len as usize]));
+        }
+        let mut times: [FILETIME; 4] = zeroed();
+        let [creation, exit, kernel, user] = &mut times;
+        if GetProcessTimes(handle, creation, exit, kernel, user) != 0 {
+            let ticks =
+                |ft: &FILETIME| (u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime);
+            // FILETIME 时长以 100 纳秒为单位
+            info.cpu_time = Some(Duration::from_nanos(
+                (ticks(&*kernel) + ticks(&*user)).saturating_mul(100),
+            ));
+        }
+        let mut counters: PROCESS_MEMORY_COUNTERS = zeroed();
+        counters.cb = size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        if K32GetProcessMemoryInfo(handle, &mut counters, counters.cb) != 0 {
+            info.memory = Some(counters.WorkingSetSize as u64);
+        }
+        CloseHandle(handle);
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, exe: &str, argv: &[&str]) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            exe: Some(exe.to_string()),
+            argv: argv.iter().map(|s| s.to_string()).collect(),
+            cpu_time: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn find_matches_executable_and_arguments() {
+        let processes = [
+            process(1, "/usr/bin/agent", &["/usr/bin/agent", "--other"]),
+            process(2, "/usr/bin/agent", &["/usr/bin/agent", "--daemon"]),
+        ];
+        let args = vec!["--daemon".to_string()];
+        assert_eq!(
+            find(&processes, "/usr/bin/agent", &args).map(|p| p.pid),
+            Some(2)
+        );
+        assert_eq!(
+            find(&processes, "/usr/bin/agent", &[]).map(|p| p.pid),
+            Some(1)
+        );
+        assert!(find(&processes, "/usr/bin/other", &[]).is_none());
+    }
+
+    #[test]
+    fn find_accepts_unreadable_command_line() {
+        let processes = [process(7, "/opt/app", &[])];
+        let args = vec!["--tray".to_string()];
+        assert_eq!(find(&processes, "/opt/app", &args).map(|p| p.pid), Some(7));
+    }
+
+    #[test]
+    fn argv0_only_counts_without_exe() {
+        // 进程可以把 argv[0] 改成任意值，可执行文件已知时以它为准
+        let processes = [process(3, "/usr/bin/other", &["/opt/app"])];
+        assert!(find(&processes, "/opt/app", &[]).is_none());
+        let mut hidden = process(4, "", &["/opt/app"]);
+        hidden.exe = None;
+        assert_eq!(find(&[hidden], "/opt/app", &[]).map(|p| p.pid), Some(4));
+    }
+
+    #[test]
+    fn stat_cpu_time_skips_process_name() {
+        let stat = "42 (my (odd) name) S 1 42 42 0 -1 4194560 100 0 0 0 250 50 0 0 20 0 1 0";
+        assert_eq!(parse_stat_cpu_time(stat), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn procargs_skips_exec_path_and_padding() {
+        let mut buf = 2i32.to_ne_bytes().to_vec();
+        buf.extend(b"/usr/bin/agent\0\0\0\0/usr/bin/agent\0--daemon\0HOME=/Users/x\0");
+        assert_eq!(
+            parse_procargs(&buf),
+            Some(vec!["/usr/bin/agent".to_string(), "--daemon".to_string()])
+        );
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(35 * 1024 * 1024 + 512 * 1024), "35.5 MB");
+        assert_eq!(format_cpu_time(Duration::from_millis(3_200)), "3.2s");
+        assert_eq!(format_cpu_time(Duration::from_secs(62)), "1m02s");
+        assert_eq!(format_cpu_time(Duration::from_secs(7_500)), "2h05m");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn snapshot_contains_current_process() {
+        let me = std::process::id();
+        let processes = snapshot();
+        let current = processes.iter().find(|p| p.pid == me).unwrap();
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(current.exe.as_deref(), Some(exe.to_str().unwrap()));
+        assert!(current.memory.is_some());
+    }
+}
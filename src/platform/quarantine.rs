@@ -0,0 +1,264 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 默认隔离目录：用户数据目录下的 `bootwatch/quarantine`
+/// （macOS `~/Library/Application Support`，Windows `%LOCALAPPDATA%`，其余 `~/.local/share`）
+pub fn default_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("bootwatch").join("quarantine"))
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 在隔离目录中为 `file_name` 选一个不冲突的目标路径：`<时间戳>-<文件名>`，重名时追加序号
+fn target_path(dir: &Path, file_name: &str) -> PathBuf {
+    let stamp = timestamp();
+    let mut target = dir.join(format!("{}-{}", stamp, file_name));
+    let mut n = 1;
+    while target.exists() {
+        target = dir.join(format!("{}-{}-{}", stamp, n, file_name));
+        n += 1;
+    }
+    target
+}
+
+/// 在 `manifest.log` 追加一行：`<时间戳>\t<原始位置>\t<隔离位置>`
+fn record(dir: &Path, original: &str, stored: &Path) -> io::Result<()> {
+    let mut manifest = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("manifest.log"))?;
+    writeln!(
+        manifest,
+        "{}\t{}\t{}",
+        timestamp(),
+        original,
+        stored.display()
+    )
+}
+
+/// 把文件（或目录，如 StartupItems bundle）移入隔离目录，返回隔离后的路径；
+/// 原始位置记录在 `manifest.log` 中，便于手工恢复。
+///
+/// 优先 `rename`；跨文件系统时退化为复制后删除原文件。
+pub fn move_file(dir: &Path, path: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "item".to_string());
+    let target = target_path(dir, &file_name);
+    if fs::rename(path, &target).is_err() {
+        if path.is_dir() {
+            copy_dir(path, &target)?;
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::copy(path, &target)?;
+            fs::remove_file(path)?;
+        }
+    }
+    record(dir, &path.display().to_string(), &target)?;
+    Ok(target)
+}
+
+/// 递归复制目录
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// 为没有定义文件的启动项（注册表值、登录项）写入备份内容，返回备份文件路径
+pub fn write_backup(
+    dir: &Path,
+    original: &str,
+    file_name: &str,
+    content: &[u8],
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let target = target_path(dir, file_name);
+    fs::write(&target, content)?;
+    record(dir, original, &target)?;
+    Ok(target)
+}
+
+/// 生成只含单个字符串值的 `.reg` 文件内容，可用 `reg import` 恢复；
+/// 写入磁盘前需经 [`utf16le_with_bom`] 编码
+#[cfg(any(target_os = "windows", test))]
+pub fn reg_value_file(key: &str, name: &str, value: &str) -> String {
+    fn escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+    format!(
+        "Windows Registry Editor Version 5.00\r\n\r\n[{}]\r\n\"{}\"=\"{}\"\r\n",
+        key,
+        escape(name),
+        escape(value)
+    )
+}
+
+/// `.reg`（Version 5.00）文件要求的 UTF-16LE 编码，带 BOM
+#[cfg(any(target_os = "windows", test))]
+pub fn utf16le_with_bom(s: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(s.encode_utf16().flat_map(|u| u.to_le_bytes()));
+    bytes
+}
+
+/// 检查 `.reg` 文件的基本格式：文件头与至少一个 `[键]` 节。
+/// 接受 UTF-16LE（带 BOM）与 UTF-8 两种编码，便于各种编辑器保存
+#[cfg(any(target_os = "windows", test))]
+pub fn check_reg_file(bytes: &[u8]) -> Result<(), String> {
+    let text = match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    };
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    match lines.next() {
+        Some("Windows Registry Editor Version 5.00") | Some("REGEDIT4") => {}
+        _ => return Err("缺少文件头 \"Windows Registry Editor Version 5.00\"".to_string()),
+    }
+    if !lines.any(|l| l.starts_with('[') && l.ends_with(']')) {
+        return Err("没有任何 [注册表键] 节".to_string());
+    }
+    Ok(())
+}
+
+/// 文件名中不允许出现的字符替换为 `_`
+pub fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "bootwatch-quarantine-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn move_file_keeps_content_and_records_manifest() {
+        let root = scratch_dir("move");
+        let source = root.join("com.example.agent.plist");
+        fs::write(&source, "<plist/>").unwrap();
+        let qdir = root.join("q");
+
+        let stored = move_file(&qdir, &source).unwrap();
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&stored).unwrap(), "<plist/>");
+        assert!(stored
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .ends_with("-com.example.agent.plist"));
+
+        let manifest = fs::read_to_string(qdir.join("manifest.log")).unwrap();
+        assert!(manifest.contains(&source.display().to_string()));
+        assert!(manifest.contains(&stored.display().to_string()));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn copy_dir_is_recursive() {
+        let root = scratch_dir("dir");
+        let bundle = root.join("Agent");
+        fs::create_dir_all(bundle.join("Resources")).unwrap();
+        fs::write(bundle.join("Agent"), "#!/bin/sh").unwrap();
+        fs::write(bundle.join("Resources").join("a.txt"), "a").unwrap();
+
+        copy_dir(&bundle, &root.join("copy")).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("copy").join("Resources").join("a.txt")).unwrap(),
+            "a"
+        );
+        let stored = move_file(&root.join("q"), &bundle).unwrap();
+        assert!(!bundle.exists());
+        assert!(stored.join("Agent").is_file());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn same_name_does_not_overwrite() {
+        let root = scratch_dir("dup");
+        let qdir = root.join("q");
+        let a = write_backup(&qdir, "a", "x.txt", b"1").unwrap();
+        let b = write_backup(&qdir, "b", "x.txt", b"2").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(fs::read_to_string(a).unwrap(), "1");
+        assert_eq!(fs::read_to_string(b).unwrap(), "2");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reg_file_escapes_name_and_value() {
+        let content = reg_value_file(
+            r"HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Run",
+            "My \"App\"",
+            r#""C:\Program Files\App\app.exe" --tray"#,
+        );
+        assert!(content.starts_with("Windows Registry Editor Version 5.00\r\n"));
+        assert!(content.contains(
+            "[HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Run]\r\n"
+        ));
+        assert!(content.contains(r#""My \"App\""="\"C:\\Program Files\\App\\app.exe\" --tray""#));
+    }
+
+    #[test]
+    fn utf16le_has_bom() {
+        assert_eq!(utf16le_with_bom("A"), vec![0xFF, 0xFE, 0x41, 0x00]);
+    }
+
+    #[test]
+    fn sanitize_replaces_reserved_characters() {
+        assert_eq!(sanitize_file_name(r"a/b\c:d"), "a_b_c_d");
+        assert_eq!(sanitize_file_name("Dropbox"), "Dropbox");
+    }
+
+    #[test]
+    fn check_reg_file_accepts_exported_value() {
+        let content = reg_value_file(r"HKEY_CURRENT_USER\Software\Run", "App", "app.exe");
+        assert!(check_reg_file(&utf16le_with_bom(&content)).is_ok());
+        assert!(check_reg_file(content.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn check_reg_file_rejects_broken_files() {
+        assert!(check_reg_file(b"\"App\"=\"app.exe\"\r\n").is_err());
+        assert!(check_reg_file(b"Windows Registry Editor Version 5.00\r\n\r\n").is_err());
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+/// 注册表值类型
+pub const REG_SZ: u32 = 1;
+pub const REG_EXPAND_SZ: u32 = 2;
+pub const REG_DWORD: u32 = 4;
+
+/// 基本块大小；cell 偏移量相对于其后的第一个 hive bin
+const BASE_BLOCK_SIZE: usize = 4096;
+/// 超过该长度的值数据存放在 `db` 分段中
+const BIG_DATA_SEGMENT: usize = 16344;
+/// nk 名称以 ASCII（Latin-1）存放
+const KEY_COMP_NAME: u16 = 0x20;
+/// vk 名称以 ASCII（Latin-1）存放
+const VALUE_COMP_NAME: u16 = 0x1;
+/// 数据直接存放在 vk 的偏移量字段中
+const DATA_INLINE: u32 = 0x8000_0000;
+/// 子键列表的嵌套层数上限，防止损坏的 `ri` 列表循环引用
+const MAX_DEPTH: usize = 8;
+/// cell 的最小长度（大小字段加 8 字节对齐）
+const MIN_CELL_SIZE: usize = 8;
+
+/// 离线注册表 hive（`NTUSER.DAT`、`SOFTWARE`、`SYSTEM` 等，regf 格式），只读。
+/// 未合并的事务日志（`.LOG1` / `.LOG2`）不会被应用
+pub struct Hive {
+    bytes: Vec<u8>,
+    root: u32,
+}
+
+impl Hive {
+    pub fn parse(bytes: Vec<u8>) -> Result<Hive, String> {
+        if !bytes.starts_with(b"regf") || bytes.len() < BASE_BLOCK_SIZE {
+            return Err("不是有效的注册表 hive 文件".to_string());
+        }
+        let root = u32_at(&bytes, 0x24).ok_or("注册表 hive 文件不完整")?;
+        let hive = Hive { bytes, root };
+        hive.key(root).ok_or("注册表 hive 缺少根键")?;
+        Ok(hive)
+    }
+
+    pub fn root(&self) -> Key<'_> {
+        Key {
+            hive: self,
+            offset: self.root,
+        }
+    }
+
+    /// 按 `\` 分隔的路径打开子键（不含根键名，忽略大小写）
+    pub fn open(&self, path: &str) -> Option<Key<'_>> {
+        path.split('\\')
+            .filter(|part| !part.is_empty())
+            .try_fold(self.root(), |key, part| key.subkey(part))
+    }
+
+    /// cell 的数据部分；未分配（大小为正）或越界时为 `None`
+    fn cell(&self, offset: u32) -> Option<&[u8]> {
+        let start = BASE_BLOCK_SIZE.checked_add(offset as usize)?;
+        let size = i32::from_le_bytes(self.bytes.get(start..start + 4)?.try_into().ok()?);
+        if size >= 0 {
+            return None;
+        }
+        self.bytes
+            .get(start + 4..start + size.unsigned_abs() as usize)
+    }
+
+    /// 带签名检查的 cell
+    fn record(&self, offset: u32, signature: &[u8; 2]) -> Option<&[u8]> {
+        self.cell(offset).filter(|cell| cell.starts_with(signature))
+    }
+
+    fn key(&self, offset: u32) -> Option<&[u8]> {
+        self.record(offset, b"nk").filter(|nk| nk.len() >= 76)
+    }
+
+    /// 子键列表（`lf` / `lh` / `li` / `ri`）中的 nk 偏移量。
+    /// 已访问过的列表不再展开，结果总数以 hive 中可能容纳的 cell 数为上限
+    fn subkey_offsets(
+        &self,
+        list: u32,
+        depth: usize,
+        visited: &mut HashSet<u32>,
+        out: &mut Vec<u32>,
+    ) {
+        if depth >= MAX_DEPTH || !visited.insert(list) {
+            return;
+        }
+        let Some(cell) = self.cell(list) else {
+            return;
+        };
+        let count = u16_at(cell, 2).unwrap_or(0) as usize;
+        let (stride, nested) = match cell.get(..2) {
+            Some(b"lf" | b"lh") => (8, false),
+            Some(b"li") => (4, false),
+            Some(b"ri") => (4, true),
+            _ => return,
+        };
+        for i in 0..count {
+            let Some(offset) = u32_at(cell, 4 + i * stride) else {
+                break;
+            };
+            if out.len() >= self.bytes.len() / MIN_CELL_SIZE {
+                return;
+            }
+            if nested {
+                self.subkey_offsets(offset, depth + 1, visited, out);
+            } else {
+                out.push(offset);
+            }
+        }
+    }
+
+    /// 值数据：内联、普通 cell 或 `db` 分段
+    fn data(&self, size: u32, offset: u32) -> Vec<u8> {
+        if size & DATA_INLINE != 0 {
+            let len = ((size & !DATA_INLINE) as usize).min(4);
+            return offset.to_le_bytes()[..len].to_vec();
+        }
+        let size = size as usize;
+        let Some(cell) = self.cell(offset) else {
+            return vec![];
+        };
+        if size > BIG_DATA_SEGMENT && cell.starts_with(b"db") {
+            let count = u16_at(cell, 2).unwrap_or(0) as usize;
+            let Some(segments) = u32_at(cell, 4).and_then(|list| self.cell(list)) else {
+                return vec![];
+            };
+            let mut data = vec![];
+            for i in 0..count {
+                let Some(segment) = u32_at(segments, i * 4).and_then(|s| self.cell(s)) else {
+                    break;
+                };
+                let want = (size - data.len()).min(BIG_DATA_SEGMENT);
+                data.extend_from_slice(&segment[..want.min(segment.len())]);
+            }
+            return data;
+        }
+        cell[..size.min(cell.len())].to_vec()
+    }
+}
+
+/// hive 中的一个键
+#[derive(Clone, Copy)]
+pub struct Key<'a> {
+    hive: &'a Hive,
+    offset: u32,
+}
+
+impl<'a> Key<'a> {
+    fn nk(&self) -> &'a [u8] {
+        // 构造 Key 时已确认 nk 有效
+        self.hive.key(self.offset).unwrap_or_default()
+    }
+
+    pub fn name(&self) -> String {
+        let nk = self.nk();
+        let len = u16_at(nk, 72).unwrap_or(0) as usize;
+        let flags = u16_at(nk, 2).unwrap_or(0);
+        let raw = nk.get(76..76 + len).unwrap_or_default();
+        decode_name(raw, flags & KEY_COMP_NAME != 0)
+    }
+
+    /// 键的最后写入时间
+    pub fn modified(&self) -> Option<SystemTime> {
+        let ticks = u64::from_le_bytes(self.nk().get(4..12)?.try_into().ok()?);
+        crate::platform::helper::filetime_to_system_time(ticks)
+    }
+
+    pub fn subkeys(&self) -> Vec<Key<'a>> {
+        let nk = self.nk();
+        let mut offsets = vec![];
+        if u32_at(nk, 20).unwrap_or(0) > 0 {
+            if let Some(list) = u32_at(nk, 28) {
+                self.hive
+                    .subkey_offsets(list, 0, &mut HashSet::new(), &mut offsets);
+            }
+        }
+        offsets
+            .into_iter()
+            .filter(|&offset| self.hive.key(offset).is_some())
+            .map(|offset| Key {
+                hive: self.hive,
+                offset,
+            })
+            .collect()
+    }
+
+    /// 名称相同（忽略大小写）的子键
+    pub fn subkey(&self, name: &str) -> Option<Key<'a>> {
+        self.subkeys()
+            .into_iter()
+            .find(|key| key.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn values(&self) -> Vec<Value> {
+        let nk = self.nk();
+        let count = u32_at(nk, 36).unwrap_or(0) as usize;
+        let Some(list) = u32_at(nk, 40).and_then(|list| self.hive.cell(list)) else {
+            return vec![];
+        };
+        // 值个数来自 nk 记录，不可信；以值列表 cell 的长度为上限
+        (0..count.min(list.len() / 4))
+            .filter_map(|i| u32_at(list, i * 4))
+            .filter_map(|offset| self.hive.record(offset, b"vk"))
+            .filter_map(|vk| {
+                let len = u16_at(vk, 2)? as usize;
+                let flags = u16_at(vk, 16)?;
+                Some(Value {
+                    name: decode_name(vk.get(20..20 + len)?, flags & VALUE_COMP_NAME != 0),
+                    data_type: u32_at(vk, 12)?,
+                    data: self.hive.data(u32_at(vk, 4)?, u32_at(vk, 8)?),
+                })
+            })
+            .collect()
+    }
+
+    /// 名称相同（忽略大小写）的值；空字符串为默认值
+    pub fn value(&self, name: &str) -> Option<Value> {
+        self.values()
+            .into_iter()
+            .find(|value| value.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// 注册表值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Value {
+    /// 默认值的名称为空字符串
+    pub name: String,
+    pub data_type: u32,
+    pub data: Vec<u8>,
+}
+
+impl Value {
+    /// `REG_SZ` / `REG_EXPAND_SZ` 的字符串内容（去掉结尾的 NUL）
+    pub fn string(&self) -> Option<String> {
+        if !matches!(self.data_type, REG_SZ | REG_EXPAND_SZ) {
+            return None;
+        }
+        let units: Vec<u16> = self
+            .data
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let end = units.iter().position(|&u| u == 0).unwrap_or(units.len());
+        Some(String::from_utf16_lossy(&units[..end]))
+    }
+
+    pub fn dword(&self) -> Option<u32> {
+        (self.data_type == REG_DWORD)
+            .then(|| u32_at(&self.data, 0))
+            .flatten()
+    }
+}
+
+fn decode_name(raw: &[u8], ascii: bool) -> String {
+    if ascii {
+        raw.iter().map(|&b| b as char).collect()
+    } else {
+        let units: Vec<u16> = raw
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    }
+}
+
+fn u16_at(bytes: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(pos..pos + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(bytes: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(pos..pos + 4)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Hive {
+        Hive::parse(include_bytes!("../../tests/fixtures/NTUSER.DAT").to_vec()).unwrap()
+    }
+
+    #[test]
+    fn opens_nested_keys_case_insensitively() {
+        let hive = fixture();
+        let run = hive
+            .open(r"software\Microsoft\Windows\CurrentVersion\Run")
+            .unwrap();
+        assert_eq!(run.name(), "Run");
+        assert!(run.modified().is_some());
+        assert!(hive.open(r"Software\Missing").is_none());
+        // 根键的子键列表为 `ri`，其下分为两个 `lh`
+        let names: Vec<String> = hive.root().subkeys().iter().map(Key::name).collect();
+        assert_eq!(names, ["AppEvents", "Console", "Software"]);
+    }
+
+    #[test]
+    fn reads_values() {
+        let hive = fixture();
+        let run = hive
+            .open(r"Software\Microsoft\Windows\CurrentVersion\Run")
+            .unwrap();
+        let values = run.values();
+        let names: Vec<&str> = values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["OneDrive", "更新程序", "Flags", "Big"]);
+        assert_eq!(
+            values[0].string().as_deref(),
+            Some(r#""C:\Users\alice\AppData\Local\Microsoft\OneDrive\OneDrive.exe" /background"#)
+        );
+        assert_eq!(
+            values[1].string().as_deref(),
+            Some(r"%ProgramFiles%\Updater\update.exe")
+        );
+        assert_eq!(values[1].data_type, REG_EXPAND_SZ);
+        // 内联存放的 DWORD
+        assert_eq!(values[2].dword(), Some(7));
+        assert_eq!(values[2].string(), None);
+        // 跨 `db` 分段的长字符串
+        let big = values[3].string().unwrap();
+        assert_eq!(big.len(), 10_000);
+        assert!(big.chars().all(|c| c == 'x'));
+        assert_eq!(run.value("onedrive"), Some(values[0].clone()));
+    }
+
+    #[test]
+    fn value_count_is_bounded_by_list_cell() {
+        let mut bytes = include_bytes!("../../tests/fixtures/NTUSER.DAT").to_vec();
+        let offset = fixture()
+            .open(r"Software\Microsoft\Windows\CurrentVersion\Run")
+            .unwrap()
+            .offset as usize;
+        // nk 数据部分第 36 字节为值个数
+        let count = BASE_BLOCK_SIZE + offset + 4 + 36;
+        bytes[count..count + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let hive = Hive::parse(bytes).unwrap();
+        let run = hive
+            .open(r"Software\Microsoft\Windows\CurrentVersion\Run")
+            .unwrap();
+        assert_eq!(run.values().len(), 4);
+    }
+
+    #[test]
+    fn self_referencing_ri_list_terminates() {
+        let mut bytes = include_bytes!("../../tests/fixtures/NTUSER.DAT").to_vec();
+        // 在文件末尾追加一个 1000 项全部指向自身的 `ri` cell，并让根键使用它
+        let ri = (bytes.len() - BASE_BLOCK_SIZE) as u32;
+        let entries = 1000u16;
+        let size = 8 + 4 * entries as i32;
+        bytes.extend((-size).to_le_bytes());
+        bytes.extend(b"ri");
+        bytes.extend(entries.to_le_bytes());
+        for _ in 0..entries {
+            bytes.extend(ri.to_le_bytes());
+        }
+        let root = u32_at(&bytes, 0x24).unwrap() as usize;
+        let list = BASE_BLOCK_SIZE + root + 4 + 28;
+        bytes[list..list + 4].copy_from_slice(&ri.to_le_bytes());
+        let hive = Hive::parse(bytes).unwrap();
+        assert!(hive.root().subkeys().is_empty());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(Hive::parse(b"not a hive".to_vec()).is_err());
+        let mut truncated = include_bytes!("../../tests/fixtures/NTUSER.DAT").to_vec();
+        truncated.truncate(BASE_BLOCK_SIZE);
+        assert!(Hive::parse(truncated).is_err());
+    }
+}
@@ -0,0 +1,374 @@
+use crate::platform::helper::{self, Trigger};
+use crate::platform::xml::{Reader, Tag};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 元素嵌套的最大深度，任务定义通常不超过 5 层
+const MAX_DEPTH: usize = 32;
+/// 目录嵌套的最大深度，防止符号链接成环
+const MAX_DIR_DEPTH: usize = 16;
+
+/// 常见内置账户与组的 SID，任务定义中多以 SID 记录运行身份
+const WELL_KNOWN_SIDS: &[(&str, &str)] = &[
+    ("S-1-5-18", "SYSTEM"),
+    ("S-1-5-19", "LOCAL SERVICE"),
+    ("S-1-5-20", "NETWORK SERVICE"),
+    ("S-1-5-32-544", "Administrators"),
+    ("S-1-5-32-545", "Users"),
+    ("S-1-5-4", "INTERACTIVE"),
+    ("S-1-5-11", "Authenticated Users"),
+];
+
+/// 任务计划程序中的一个任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    /// 任务路径（如 `\Vendor\Updater`），即定义文件相对 `Tasks` 目录的路径，`schtasks /TN` 使用
+    pub path: String,
+    /// 定义文件
+    pub file: PathBuf,
+    pub modified: Option<SystemTime>,
+    pub definition: Definition,
+}
+
+impl Task {
+    /// 任务名（路径的最后一段）
+    pub fn name(&self) -> &str {
+        self.path.rsplit('\\').next().unwrap_or(&self.path)
+    }
+
+    /// 所在文件夹，根文件夹为 `\`
+    pub fn folder(&self) -> &str {
+        match self.path.rfind('\\') {
+            Some(0) | None => "\\",
+            Some(i) => &self.path[..i],
+        }
+    }
+}
+
+/// 任务定义 XML 中与启动相关的内容
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Definition {
+    /// 已启用的开机（`BootTrigger`）与登录（`LogonTrigger`）触发器，其它触发器不记录
+    pub triggers: Vec<Trigger>,
+    /// 各动作的描述：`Exec` 为命令行，其它动作为 `ComHandler {CLSID}` 等
+    pub actions: Vec<String>,
+    /// 第一个 `Exec` 动作的命令行
+    pub command: Option<String>,
+    /// 运行身份（`Actions` 的 `Context` 指向的 Principal），如 `SYSTEM (HighestAvailable)`
+    pub principal: Option<String>,
+    /// `Settings/Enabled`，缺省为启用
+    pub enabled: bool,
+    /// `RegistrationInfo/Author`
+    pub author: Option<String>,
+}
+
+/// 递归读取 `dir`（`Windows\System32\Tasks`）中的任务定义，只保留有开机或登录触发器的任务。
+/// 无权读取（非管理员运行时常见）或无法解析的文件被跳过
+pub fn scan(dir: &Path) -> Result<Vec<Task>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("无法读取任务目录 {}: {}", dir.display(), e))?;
+    let mut tasks = vec![];
+    collect(entries, "", 0, &mut tasks);
+    tasks.sort_by_key(|task| task.path.to_lowercase());
+    Ok(tasks)
+}
+
+fn collect(entries: fs::ReadDir, folder: &str, depth: usize, tasks: &mut Vec<Task>) {
+    for entry in entries.flatten() {
+        let file = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = format!("{}\\{}", folder, name);
+        if file.is_dir() {
+            if let (true, Ok(entries)) = (depth < MAX_DIR_DEPTH, fs::read_dir(&file)) {
+                collect(entries, &path, depth + 1, tasks);
+            }
+            continue;
+        }
+        let Some(definition) = fs::read(&file).ok().and_then(|b| parse(&b).ok()) else {
+            continue;
+        };
+        if definition.triggers.is_empty() {
+            continue;
+        }
+        tasks.push(Task {
+            path,
+            modified: helper::modified_time(&file),
+            file,
+            definition,
+        });
+    }
+}
+
+/// 解析任务定义 XML（通常为带 BOM 的 UTF-16LE，也接受 UTF-8）
+pub fn parse(bytes: &[u8]) -> Result<Definition, String> {
+    let text = decode(bytes);
+    let root = document(&text)?;
+    if root.name != "Task" {
+        return Err(format!("不是任务定义，根元素为 <{}>", root.name));
+    }
+
+    let triggers = root
+        .child("Triggers")
+        .map(|triggers| {
+            triggers
+                .children
+                .iter()
+                // 触发器自身的 Enabled 为 false 时不会触发
+                .filter(|t| t.text_of("Enabled").as_deref() != Some("false"))
+                .filter_map(|t| match t.name.as_str() {
+                    "BootTrigger" => Some(Trigger::Boot),
+                    "LogonTrigger" => Some(Trigger::Logon),
+                    _ => None,
+                })
+                .fold(vec![], |mut out, t| {
+                    if !out.contains(&t) {
+                        out.push(t);
+                    }
+                    out
+                })
+        })
+        .unwrap_or_default();
+
+    let actions_element = root.child("Actions");
+    let actions: Vec<String> = actions_element
+        .map(|actions| actions.children.iter().filter_map(action).collect())
+        .unwrap_or_default();
+    let command = actions_element
+        .and_then(|actions| actions.children.iter().find(|a| a.name == "Exec"))
+        .and_then(action);
+
+    // Actions 的 Context 属性指向执行动作的 Principal，缺省时取第一个
+    let context = actions_element.and_then(|a| a.attribute("Context"));
+    let principal = root.child("Principals").and_then(|principals| {
+        let mut all = principals.children.iter().filter(|p| p.name == "Principal");
+        match context {
+            Some(id) => all.find(|p| p.attribute("id") == Some(id)),
+            None => all.next(),
+        }
+        .and_then(principal)
+    });
+
+    Ok(Definition {
+        triggers,
+        actions,
+        command,
+        principal,
+        enabled: root
+            .child("Settings")
+            .and_then(|s| s.text_of("Enabled"))
+            .as_deref()
+            != Some("false"),
+        author: root
+            .child("RegistrationInfo")
+            .and_then(|r| r.text_of("Author")),
+    })
+}
+
+/// 动作的描述；`Exec` 的命令含空白且未加引号时补上引号，与 `helper::executable_of` 对应
+fn action(element: &Element) -> Option<String> {
+    match element.name.as_str() {
+        "Exec" => {
+            let command = element.text_of("Command")?;
+            let command = if command.contains(char::is_whitespace) && !command.starts_with('"') {
+                format!("\"{}\"", command)
+            } else {
+                command
+            };
+            Some(match element.text_of("Arguments") {
+                Some(args) => format!("{} {}", command, args),
+                None => command,
+            })
+        }
+        "ComHandler" => Some(match element.text_of("ClassId") {
+            Some(class_id) => format!("ComHandler {}", class_id),
+            None => "ComHandler".to_string(),
+        }),
+        // SendEmail / ShowMessage 已被弃用，只记录动作类型
+        name => Some(name.to_string()),
+    }
+}
+
+/// Principal 的账户（或组）与运行级别
+fn principal(element: &Element) -> Option<String> {
+    let account = element
+        .text_of("UserId")
+        .or_else(|| element.text_of("GroupId"))?;
+    let account = WELL_KNOWN_SIDS
+        .iter()
+        .find(|(sid, _)| sid.eq_ignore_ascii_case(&account))
+        .map_or(account, |(_, name)| name.to_string());
+    Some(match element.text_of("RunLevel").as_deref() {
+        Some("HighestAvailable") => format!("{} (HighestAvailable)", account),
+        _ => account,
+    })
+}
+
+/// 按 BOM 识别编码；没有 BOM 时按第二个字节是否为 0 区分 UTF-16LE 与 UTF-8
+fn decode(bytes: &[u8]) -> String {
+    let utf16 = |data: &[u8], be: bool| {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|c| {
+                if be {
+                    u16::from_be_bytes([c[0], c[1]])
+                } else {
+                    u16::from_le_bytes([c[0], c[1]])
+                }
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, false),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, true),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [_, 0, ..] => utf16(bytes, false),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// XML 元素；命名空间前缀保留在名称中（任务定义使用默认命名空间，不带前缀）
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// 子元素去掉首尾空白后的文本，为空时为 `None`
+    fn text_of(&self, name: &str) -> Option<String> {
+        self.child(name)
+            .map(|c| c.text.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 解析根元素，构建元素树
+fn document(text: &str) -> Result<Element, String> {
+    let mut xml = Reader::new(text, "任务定义格式错误");
+    let tag = xml.tag()?;
+    let root = element(&mut xml, tag, 0)?;
+    xml.end()?;
+    Ok(root)
+}
+
+/// 解析以 `tag` 开始的元素及其内容
+fn element(xml: &mut Reader<'_>, tag: Tag<'_>, depth: usize) -> Result<Element, String> {
+    if depth > MAX_DEPTH {
+        return Err(xml.error("元素嵌套过深"));
+    }
+    let (name, empty) = match tag {
+        Tag::Open(name) => (name, false),
+        Tag::Empty(name) => (name, true),
+        Tag::Close(name) => return Err(xml.error(format!("意外的标签 </{}>", name))),
+    };
+    let mut element = Element {
+        name: name.to_string(),
+        attributes: xml.attributes()?,
+        ..Element::default()
+    };
+    if empty {
+        return Ok(element);
+    }
+    loop {
+        xml.text(name, &mut element.text)?;
+        match xml.tag()? {
+            Tag::Close(close) if close == name => return Ok(element),
+            Tag::Close(close) => {
+                return Err(xml.error(format!("此处应为 </{}>，实际为 </{}>", name, close)))
+            }
+            tag => element.children.push(self::element(xml, tag, depth + 1)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VENDOR: &[u8] = include_bytes!("../../tests/fixtures/tasks/VendorUpdate.xml");
+    const BOOT: &[u8] = include_bytes!("../../tests/fixtures/tasks/BootCheck.xml");
+    const DAILY: &[u8] = include_bytes!("../../tests/fixtures/tasks/Daily.xml");
+
+    #[test]
+    fn parses_logon_task() {
+        let definition = parse(VENDOR).unwrap();
+        assert_eq!(definition.triggers, [Trigger::Logon]);
+        assert_eq!(
+            definition.command.as_deref(),
+            Some(r#""C:\Program Files\Vendor\update.exe" /silent /source=logon"#)
+        );
+        assert_eq!(definition.actions.len(), 1);
+        assert_eq!(
+            definition.principal.as_deref(),
+            Some("SYSTEM (HighestAvailable)")
+        );
+        assert!(definition.enabled);
+        assert_eq!(definition.author.as_deref(), Some("Vendor & Co."));
+    }
+
+    #[test]
+    fn parses_boot_task() {
+        let definition = parse(BOOT).unwrap();
+        // 已停用的 LogonTrigger 不计入
+        assert_eq!(definition.triggers, [Trigger::Boot]);
+        assert_eq!(
+            definition.actions,
+            [
+                "ComHandler {A6BA00FE-40E8-477C-B713-C64A14F18ADB}",
+                r#""%windir%\system32\cmd.exe" /c check.cmd > "%TEMP%\check.log""#
+            ]
+        );
+        assert_eq!(definition.command.as_ref(), definition.actions.get(1));
+        // Actions 的 Context 选中第二个 Principal
+        assert_eq!(definition.principal.as_deref(), Some("SYSTEM"));
+        assert!(!definition.enabled);
+        assert_eq!(definition.author, None);
+
+        assert!(parse(DAILY).unwrap().triggers.is_empty());
+    }
+
+    #[test]
+    fn scans_task_folders() {
+        let dir = std::env::temp_dir().join(format!("bootwatch-tasks-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Vendor")).unwrap();
+        fs::write(dir.join("VendorUpdate"), VENDOR).unwrap();
+        fs::write(dir.join("Vendor").join("BootCheck"), BOOT).unwrap();
+        fs::write(dir.join("Daily"), DAILY).unwrap();
+        fs::write(dir.join("Broken"), "<Task>").unwrap();
+
+        let tasks = scan(&dir).unwrap();
+        let paths: Vec<&str> = tasks.iter().map(|t| t.path.as_str()).collect();
+        assert_eq!(paths, [r"\Vendor\BootCheck", r"\VendorUpdate"]);
+        assert_eq!(tasks[0].name(), "BootCheck");
+        assert_eq!(tasks[0].folder(), r"\Vendor");
+        assert_eq!(tasks[1].folder(), r"\");
+        assert!(tasks[1].modified.is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(scan(&dir).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_xml() {
+        assert!(parse(b"<Task><Triggers></Task>").is_err());
+        assert!(parse(b"<Other/>").is_err());
+        assert!(parse(b"<Task a=b/>").is_err());
+        assert!(parse("<Task/>".as_bytes()).unwrap().triggers.is_empty());
+    }
+}
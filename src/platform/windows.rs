@@ -1,7 +1,14 @@
-use crate::platform::helper::{parse_token, OptionItem};
+use crate::config::SourceConfig;
+use crate::platform::command::Runner;
+use crate::platform::helper::{self, parse_token, OptionItem, Scope};
+use crate::platform::lnk;
+use crate::platform::quarantine;
+use crate::platform::tasks;
+use crate::platform::EditTarget;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE}; // Import HKCU and HKLM
 use winreg::RegKey;
 
@@ -10,6 +17,7 @@ use winreg::RegKey;
 pub enum StartupType {
     Registry,      // Registry-based startup item
     StartupFolder, // Startup folder shortcut
+    ScheduledTask, // Task Scheduler task with a boot or logon trigger
 }
 
 /// 表示一个 Windows 启动项
@@ -17,72 +25,246 @@ pub enum StartupType {
 pub struct StartupItem {
     pub label: String,
     pub path: Option<String>,
+    /// Registry 项的值即启动命令行；StartupFolder 中的快捷方式为其目标与参数；
+    /// 计划任务为第一个 `Exec` 动作
+    pub command: Option<String>,
+    pub scope: Scope,
+    /// 所在注册表键或目录
+    pub location: Option<String>,
+    pub modified: Option<SystemTime>,
+    /// 已被禁用（仍保留定义）
+    pub disabled: bool,
+    /// 所在自启动键的简称（如 `RunOnce`），仅 Registry 项
+    pub kind: Option<String>,
+    /// 任务管理器「启动」页不显示（`RunOnce`、`Policies\Explorer\Run` 等）
+    pub unlisted: bool,
+    /// 计划任务的开机 / 登录触发器
+    pub triggers: Vec<helper::Trigger>,
+    /// 计划任务的作者
+    pub developer: Option<String>,
+    /// 计划任务的运行身份
+    pub principal: Option<String>,
     pub item_type: StartupType,
     /// 平台相关的删除令牌，格式：
     /// - Registry: `reg|<完整键路径>|<值名>`
     /// - StartupFolder: `file|<文件路径>`
+    /// - ScheduledTask: `task|<任务路径>`
     pub delete_value: String,
 }
 
+/// 扫描单个来源，`id` 为 `platform::SOURCES` 中的来源 ID。
+/// 注册表与目录都在进程内读取，不经过外部命令
 #[cfg(target_os = "windows")]
-pub fn get_startup_apps() -> Vec<StartupItem> {
+pub fn scan_source(
+    id: &str,
+    source: &SourceConfig,
+    _runner: &Runner,
+) -> Result<Vec<StartupItem>, String> {
+    match id {
+        "registry" => Ok(get_registry_items()),
+        "startup_folder" => {
+            let extra = source
+                .extra_dirs
+                .iter()
+                .map(|dir| PathBuf::from(shellexpand::tilde(dir).into_owned()));
+            let user = get_startup_folder().into_iter().chain(extra);
+            Ok(user
+                .map(|dir| (dir, Scope::User))
+                .chain(get_common_startup_folder().map(|dir| (dir, Scope::System)))
+                .flat_map(|(dir, scope)| get_folder_items(&dir, scope))
+                .collect())
+        }
+        "scheduled_task" => get_scheduled_tasks(),
+        _ => Err(format!("未知来源: {}", id)),
+    }
+}
+
+/// `helper::RUN_KEYS` 中各自启动键的启动项，`HKCU` 与 `HKLM` 各一份
+fn get_registry_items() -> Vec<StartupItem> {
     let mut items = vec![];
 
-    // 注册表启动项：同时记录 hive 名称，以便删除时构造完整键路径
-    let reg_paths = vec![
-        (
-            HKEY_CURRENT_USER,
-            "HKEY_CURRENT_USER",
-            r"Software\Microsoft\Windows\CurrentVersion\Run",
-        ),
-        (
-            HKEY_LOCAL_MACHINE,
-            "HKEY_LOCAL_MACHINE",
-            r"Software\Microsoft\Windows\CurrentVersion\Run",
-        ),
+    // 同时记录 hive 名称，以便删除时构造完整键路径
+    let hives = [
+        (HKEY_CURRENT_USER, "HKEY_CURRENT_USER", Scope::User),
+        (HKEY_LOCAL_MACHINE, "HKEY_LOCAL_MACHINE", Scope::System),
     ];
 
-    for (hive, hive_name, path) in reg_paths {
-        if let Ok(key) = RegKey::predef(hive).open_subkey(path) {
-            let full_key = format!("{}\\{}", hive_name, path);
-            for name in key.enum_values().flatten().map(|(name, _)| name) {
-                if let Ok(value) = key.get_value::<String, _>(&name) {
-                    items.push(StartupItem {
-                        delete_value: format!("reg|{}|{}", full_key, name),
-                        label: name,
-                        path: Some(value),
-                        item_type: StartupType::Registry,
-                    });
+    for (hive, hive_name, scope) in hives {
+        for run_key in helper::RUN_KEYS {
+            let path = format!("Software\\{}", run_key.path);
+            let Ok(key) = RegKey::predef(hive).open_subkey(&path) else {
+                continue;
+            };
+            // RunOnceEx 的命令位于编号子键中，每个子键的默认值为标题
+            let keys: Vec<(String, RegKey)> = if run_key.nested {
+                key.enum_keys()
+                    .flatten()
+                    .filter_map(|name| {
+                        let sub = key.open_subkey(&name).ok()?;
+                        Some((format!("{}\\{}", path, name), sub))
+                    })
+                    .collect()
+            } else {
+                vec![(path, key)]
+            };
+            for (path, key) in keys {
+                let full_key = format!("{}\\{}", hive_name, path);
+                // 注册表值没有各自的时间戳，使用所在键的最后写入时间
+                let modified = key.query_info().ok().and_then(|info| {
+                    let ft = &info.last_write_time;
+                    helper::filetime_to_system_time(
+                        (u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime),
+                    )
+                });
+                for name in key.enum_values().flatten().map(|(name, _)| name) {
+                    if run_key.nested && name.is_empty() {
+                        continue;
+                    }
+                    if let Ok(value) = key.get_value::<String, _>(&name) {
+                        items.push(StartupItem {
+                            disabled: run_key
+                                .approved
+                                .is_some_and(|subkey| is_disabled(hive_name, subkey, &name)),
+                            delete_value: format!("reg|{}|{}", full_key, name),
+                            label: name,
+                            command: Some(value.clone()),
+                            path: Some(value),
+                            scope,
+                            location: Some(full_key.clone()),
+                            modified,
+                            kind: Some(run_key.name.to_string()),
+                            unlisted: run_key.approved.is_none(),
+                            triggers: vec![],
+                            developer: None,
+                            principal: None,
+                            item_type: StartupType::Registry,
+                        });
+                    }
                 }
             }
         }
     }
 
-    // Check Startup folder
-    if let Some(startup_dir) = get_startup_folder() {
-        if startup_dir.exists() && startup_dir.is_dir() {
-            if let Ok(entries) = fs::read_dir(startup_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_file() {
-                        if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                            let path_str = path.display().to_string();
-                            items.push(StartupItem {
-                                delete_value: format!("file|{}", path_str),
-                                label: file_name.to_string(),
-                                path: Some(path_str),
-                                item_type: StartupType::StartupFolder,
-                            });
-                        }
+    items
+}
+
+/// Startup 文件夹（或配置中追加的目录）中的启动项；启用状态按 `scope`
+/// 取自 `HKCU` 或 `HKLM` 下的 `StartupApproved\StartupFolder`
+fn get_folder_items(startup_dir: &Path, scope: Scope) -> Vec<StartupItem> {
+    let hive_name = match scope {
+        Scope::User => "HKEY_CURRENT_USER",
+        Scope::System => "HKEY_LOCAL_MACHINE",
+    };
+    let mut items = vec![];
+    if startup_dir.exists() && startup_dir.is_dir() {
+        if let Ok(entries) = fs::read_dir(startup_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() {
+                    if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
+                        let path_str = path.display().to_string();
+                        items.push(StartupItem {
+                            command: shortcut_command(&path),
+                            disabled: is_disabled(hive_name, "StartupFolder", file_name),
+                            delete_value: format!("file|{}", path_str),
+                            label: file_name.to_string(),
+                            path: Some(path_str),
+                            scope,
+                            location: Some(startup_dir.display().to_string()),
+                            modified: helper::modified_time(&path),
+                            kind: None,
+                            unlisted: false,
+                            triggers: vec![],
+                            developer: None,
+                            principal: None,
+                            item_type: StartupType::StartupFolder,
+                        });
                     }
                 }
             }
         }
     }
-
     items
 }
 
+/// 任务计划程序中有开机或登录触发器的任务，读取 `%SystemRoot%\System32\Tasks` 中的定义。
+/// 非管理员运行时部分任务无权读取，会被跳过
+fn get_scheduled_tasks() -> Result<Vec<StartupItem>, String> {
+    Ok(tasks::scan(&tasks_dir())?
+        .into_iter()
+        .map(|task| {
+            let label = task.name().to_string();
+            let location = task.folder().to_string();
+            let definition = task.definition;
+            StartupItem {
+                label,
+                path: Some(definition.actions.join("; ")),
+                command: definition.command,
+                scope: Scope::System,
+                location: Some(location),
+                modified: task.modified,
+                disabled: !definition.enabled,
+                kind: None,
+                unlisted: false,
+                triggers: definition.triggers,
+                developer: definition.author,
+                principal: definition.principal,
+                item_type: StartupType::ScheduledTask,
+                delete_value: format!("task|{}", task.path),
+            }
+        })
+        .collect())
+}
+
+/// 计划任务定义所在目录
+fn tasks_dir() -> PathBuf {
+    std::env::var("SystemRoot")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(r"C:\Windows"))
+        .join("System32")
+        .join("Tasks")
+}
+
+/// 通过 `schtasks` 修改计划任务，失败时返回带 `action` 前缀的错误
+#[cfg(target_os = "windows")]
+fn schtasks(
+    args: &[&str],
+    action: &str,
+    item: &OptionItem,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use encoding_rs::GBK;
+
+    let output = runner.output(Command::new("schtasks").args(args))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let (decoded_stderr, _, _) = GBK.decode(&output.stderr);
+        Err(format!(
+            "{}: {}\n错误: {}",
+            action,
+            item.label,
+            decoded_stderr.trim()
+        )
+        .into())
+    }
+}
+
+/// `.lnk` 快捷方式实际启动的命令行；其它文件或无法解析时为 `None`
+fn shortcut_command(path: &Path) -> Option<String> {
+    let is_lnk = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("lnk"));
+    if !is_lnk {
+        return None;
+    }
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| lnk::parse(&bytes).ok())
+        .and_then(|shortcut| shortcut.command_in(path.parent()?))
+}
+
 /// Get the path to the user's Startup folder
 fn get_startup_folder() -> Option<PathBuf> {
     std::env::var("APPDATA")
@@ -97,10 +279,40 @@ fn get_startup_folder() -> Option<PathBuf> {
         .ok()
 }
 
+/// 所有用户共用的 Startup 文件夹（`%ProgramData%\...\StartUp`）
+fn get_common_startup_folder() -> Option<PathBuf> {
+    std::env::var("ProgramData")
+        .map(|data| {
+            PathBuf::from(data)
+                .join("Microsoft")
+                .join("Windows")
+                .join("Start Menu")
+                .join("Programs")
+                .join("StartUp")
+        })
+        .ok()
+}
+
+/// 按完整键路径（如 `HKEY_CURRENT_USER\Software\...`）打开注册表键
+fn open_key(full_key: &str) -> Option<RegKey> {
+    let (hive_name, path) = full_key.split_once('\\')?;
+    let hive = match hive_name {
+        "HKEY_CURRENT_USER" => HKEY_CURRENT_USER,
+        "HKEY_LOCAL_MACHINE" => HKEY_LOCAL_MACHINE,
+        _ => return None,
+    };
+    RegKey::predef(hive).open_subkey(path).ok()
+}
+
 /// 删除指定的开机启动项。
-/// 参数：item - 要删除的启动项，其 `value` 为 `reg|<键路径>|<值名>` 或 `file|<文件路径>`。
+/// 参数：item - 要删除的启动项，其 `value` 为 `reg|<键路径>|<值名>` 或 `file|<文件路径>`；
+/// quarantine - 隔离目录，设置时注册表值先导出为 `.reg` 备份，文件移入该目录而非直接删除。
 #[cfg(target_os = "windows")]
-pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::Error>> {
+pub fn delete_startup_item(
+    item: &OptionItem,
+    quarantine: Option<&Path>,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
     use encoding_rs::GBK;
 
     // 令牌格式：<kind>|<payload>
@@ -110,9 +322,21 @@ pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::
         "reg" => {
             // payload = `<完整键路径>|<值名>`，仅按第一个 `|` 拆分，值名中可含 `|`
             let (key_path, value_name) = payload.split_once('|').ok_or("注册表删除令牌格式错误")?;
-            let output = Command::new("reg")
-                .args(["delete", key_path, "/v", value_name, "/f"])
-                .output()?;
+            if let Some(dir) = quarantine {
+                let value: String = open_key(key_path)
+                    .ok_or_else(|| format!("无法打开注册表键: {}", key_path))?
+                    .get_value(value_name)?;
+                let content = quarantine::reg_value_file(key_path, value_name, &value);
+                let file_name = format!("{}.reg", quarantine::sanitize_file_name(value_name));
+                quarantine::write_backup(
+                    dir,
+                    &item.value,
+                    &file_name,
+                    &quarantine::utf16le_with_bom(&content),
+                )?;
+            }
+            let output = runner
+                .output(Command::new("reg").args(["delete", key_path, "/v", value_name, "/f"]))?;
 
             if output.status.success() {
                 Ok(())
@@ -127,9 +351,217 @@ pub fn delete_startup_item(item: &OptionItem) -> Result<(), Box<dyn std::error::
             }
         }
         "file" => {
-            fs::remove_file(payload)?;
+            match quarantine {
+                Some(dir) => {
+                    quarantine::move_file(dir, Path::new(payload))?;
+                }
+                None => fs::remove_file(payload)?,
+            }
             Ok(())
         }
+        "task" => {
+            // 备份任务定义 XML，可用 `schtasks /Create /XML` 恢复
+            if let Some(dir) = quarantine {
+                let file = tasks_dir().join(payload.trim_start_matches('\\'));
+                let name = payload.rsplit('\\').next().unwrap_or(payload);
+                quarantine::write_backup(
+                    dir,
+                    &item.value,
+                    &format!("{}.xml", quarantine::sanitize_file_name(name)),
+                    &fs::read(&file)?,
+                )?;
+            }
+            schtasks(
+                &["/Delete", "/TN", payload, "/F"],
+                "删除计划任务失败",
+                item,
+                runner,
+            )
+        }
         _ => Err(format!("不支持的启动项类型: {}", item.value).into()),
     }
 }
+
+/// 资源管理器记录启动项启用状态的键（任务管理器「启动」页使用同一位置）
+const STARTUP_APPROVED: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved";
+
+/// `StartupApproved` 中表示禁用的值：首字节 `03`，其余为禁用时间（留空）
+const DISABLED_FLAG: &str = "030000000000000000000000";
+
+/// `StartupApproved\<subkey>` 中是否把 `name` 标记为禁用
+fn is_disabled(hive_name: &str, subkey: &str, name: &str) -> bool {
+    open_key(&format!("{}\\{}\\{}", hive_name, STARTUP_APPROVED, subkey))
+        .and_then(|key| key.get_raw_value(name).ok())
+        .is_some_and(|value| is_disabled_flag(&value.bytes))
+}
+
+/// `StartupApproved` 值的首字节：偶数（`02`/`06`）为启用，奇数（`03`/`07`）为禁用
+fn is_disabled_flag(bytes: &[u8]) -> bool {
+    bytes.first().is_some_and(|b| b & 1 == 1)
+}
+
+/// 禁用指定的开机启动项：在 `StartupApproved` 中写入禁用标记，保留原有的注册表值或文件；
+/// 计划任务通过 `schtasks /Change /Disable` 禁用。
+/// Registry 项写入同一 hive 下该键对应的子键（`Run` 或 `Run32`），任务管理器不显示的键
+/// （`RunOnce` 等）没有禁用标记，只能删除；StartupFolder 项写入 `HKCU`（公共 Startup 文件夹
+/// 为 `HKLM`）下的 `StartupApproved\StartupFolder`（值名为文件名）。
+#[cfg(target_os = "windows")]
+pub fn disable_startup_item(
+    item: &OptionItem,
+    runner: &Runner,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use encoding_rs::GBK;
+
+    let (kind, payload) = parse_token(&item.value);
+    let (approved_key, value_name) = match kind {
+        "task" => {
+            return schtasks(
+                &["/Change", "/TN", payload, "/Disable"],
+                "禁用计划任务失败",
+                item,
+                runner,
+            );
+        }
+        "reg" => {
+            let (key_path, value_name) = payload.split_once('|').ok_or("注册表删除令牌格式错误")?;
+            let (hive_name, _) = key_path.split_once('\\').ok_or("注册表键路径格式错误")?;
+            let subkey = helper::run_key_of(key_path)
+                .and_then(|key| key.approved)
+                .ok_or_else(|| {
+                    format!(
+                        "任务管理器不管理该位置的启动项，无法禁用，只能删除: {}",
+                        key_path
+                    )
+                })?;
+            (
+                format!("{}\\{}\\{}", hive_name, STARTUP_APPROVED, subkey),
+                value_name.to_string(),
+            )
+        }
+        "file" => {
+            let path = Path::new(payload);
+            let file_name = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .ok_or("文件路径格式错误")?;
+            let common = get_common_startup_folder().is_some_and(|dir| path.starts_with(dir));
+            let hive_name = if common {
+                "HKEY_LOCAL_MACHINE"
+            } else {
+                "HKEY_CURRENT_USER"
+            };
+            (
+                format!("{}\\{}\\StartupFolder", hive_name, STARTUP_APPROVED),
+                file_name.to_string(),
+            )
+        }
+        _ => return Err(format!("不支持的启动项类型: {}", item.value).into()),
+    };
+    let output = runner.output(Command::new("reg").args([
+        "add",
+        &approved_key,
+        "/v",
+        &value_name,
+        "/t",
+        "REG_BINARY",
+        "/d",
+        DISABLED_FLAG,
+        "/f",
+    ]))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let (decoded_stderr, _, _) = GBK.decode(&output.stderr);
+        Err(format!(
+            "禁用启动项失败: {}\n错误: {}",
+            item.label,
+            decoded_stderr.trim()
+        )
+        .into())
+    }
+}
+
+/// 可在编辑器中打开的定义：注册表值导出为临时 `.reg` 文件，编辑后可导入；
+/// Startup 文件夹中的脚本直接编辑；快捷方式（二进制文件）与计划任务不支持编辑
+#[cfg(target_os = "windows")]
+pub fn edit_target(item: &OptionItem) -> Result<EditTarget, String> {
+    let (kind, payload) = parse_token(&item.value);
+    match kind {
+        "reg" => {
+            let (key_path, value_name) = payload.split_once('|').ok_or("注册表令牌格式错误")?;
+            let value: String = open_key(key_path)
+                .ok_or_else(|| format!("无法打开注册表键: {}", key_path))?
+                .get_value(value_name)
+                .map_err(|e| format!("无法读取注册表值 {}: {}", value_name, e))?;
+            let file = std::env::temp_dir().join(format!(
+                "bootwatch-{}.reg",
+                quarantine::sanitize_file_name(value_name)
+            ));
+            let content = quarantine::reg_value_file(key_path, value_name, &value);
+            fs::write(&file, quarantine::utf16le_with_bom(&content))
+                .map_err(|e| format!("无法写入 {}: {}", file.display(), e))?;
+            Ok(EditTarget::RegExport(file))
+        }
+        "file" => {
+            let path = PathBuf::from(payload);
+            let is_link = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+            if is_link {
+                Err(format!(
+                    "快捷方式是二进制文件，无法在编辑器中编辑: {}",
+                    item.label
+                ))
+            } else {
+                Ok(EditTarget::File(path))
+            }
+        }
+        "task" => Err(format!(
+            "计划任务由任务计划程序管理，请在任务计划程序中编辑: {}",
+            item.label
+        )),
+        _ => Err(format!("不支持的启动项类型: {}", item.value)),
+    }
+}
+
+/// 校验编辑后的内容：`.reg` 文件检查基本格式，脚本文件不做校验
+#[cfg(target_os = "windows")]
+pub fn validate_edit(target: &EditTarget, _runner: &Runner) -> Result<(), String> {
+    match target {
+        EditTarget::File(_) => Ok(()),
+        EditTarget::RegExport(path) => {
+            let bytes =
+                fs::read(path).map_err(|e| format!("无法读取 {}: {}", path.display(), e))?;
+            quarantine::check_reg_file(&bytes)
+        }
+    }
+}
+
+/// 通过 `reg import` 导入编辑后的 `.reg` 文件
+#[cfg(target_os = "windows")]
+pub fn import_reg(path: &Path, runner: &Runner) -> Result<(), Box<dyn std::error::Error>> {
+    use encoding_rs::GBK;
+
+    let output = runner.output(Command::new("reg").arg("import").arg(path))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let (err, _, _) = GBK.decode(&output.stderr);
+        Err(format!("导入注册表失败: {}", err.trim()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_disabled_flag;
+
+    #[test]
+    fn startup_approved_flags() {
+        assert!(!is_disabled_flag(&[0x02, 0, 0]));
+        assert!(!is_disabled_flag(&[0x06]));
+        assert!(is_disabled_flag(&[0x03, 0, 0]));
+        assert!(is_disabled_flag(&[0x07]));
+        assert!(!is_disabled_flag(&[]));
+    }
+}
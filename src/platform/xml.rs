@@ -0,0 +1,264 @@
+/// 一个 XML 标签，属性通过 `Reader::attributes` 读取
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tag<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    /// 自闭合，如 `<true/>`
+    Empty(&'a str),
+}
+
+/// plist 与任务定义共用的简单 XML 词法分析器：支持属性、实体、CDATA 与注释，不处理 DTD
+pub struct Reader<'a> {
+    text: &'a str,
+    pos: usize,
+    /// 错误信息的前缀，如 `plist 格式错误`
+    context: &'static str,
+    /// 最近读取的标签中标签名之后的原文
+    attributes: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(text: &'a str, context: &'static str) -> Self {
+        Reader {
+            text,
+            pos: 0,
+            context,
+            attributes: "",
+        }
+    }
+
+    /// 带当前行号的错误信息
+    pub fn error(&self, message: impl std::fmt::Display) -> String {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        format!("{}（第 {} 行）: {}", self.context, line, message)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// 跳过 `end` 之前的内容（含 `end`）
+    fn skip_past(&mut self, end: &str) -> Result<(), String> {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                Ok(())
+            }
+            None => Err(self.error(format!("缺少 {}", end))),
+        }
+    }
+
+    /// 跳过空白、XML 声明、DOCTYPE 与注释
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.text.len() - trimmed.len();
+            if trimmed.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if trimmed.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if trimmed.starts_with("<!") && !trimmed.starts_with("<![CDATA[") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 确认根元素之后只剩空白、注释等内容
+    pub fn end(&mut self) -> Result<(), String> {
+        self.skip_misc()?;
+        if self.pos < self.text.len() {
+            return Err(self.error("文档结尾有多余内容"));
+        }
+        Ok(())
+    }
+
+    /// 读取下一个标签，之前的空白与注释被跳过
+    pub fn tag(&mut self) -> Result<Tag<'a>, String> {
+        self.skip_misc()?;
+        if !self.rest().starts_with('<') {
+            return Err(self.error("此处应为标签"));
+        }
+        let end = tag_end(self.rest()).ok_or_else(|| self.error("标签未闭合"))?;
+        let inner = &self.rest()[1..end];
+        self.pos += end + 1;
+        let (inner, close, empty) = match (inner.strip_prefix('/'), inner.strip_suffix('/')) {
+            (Some(name), _) => (name, true, false),
+            (None, Some(name)) => (name, false, true),
+            (None, None) => (inner, false, false),
+        };
+        let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+        let name = &inner[..name_end];
+        if name.is_empty() {
+            return Err(self.error("标签名为空"));
+        }
+        self.attributes = &inner[name_end..];
+        Ok(match (close, empty) {
+            (true, _) => Tag::Close(name),
+            (_, true) => Tag::Empty(name),
+            _ => Tag::Open(name),
+        })
+    }
+
+    pub fn expect_close(&mut self, name: &str) -> Result<(), String> {
+        match self.tag()? {
+            Tag::Close(n) if n == name => Ok(()),
+            other => Err(self.error(format!("此处应为 </{}>，实际为 {:?}", name, other))),
+        }
+    }
+
+    /// 解析最近读取的标签的 `name="value"` 属性列表
+    pub fn attributes(&self) -> Result<Vec<(String, String)>, String> {
+        let mut attributes = vec![];
+        let mut rest = self.attributes;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return Ok(attributes);
+            }
+            let eq = rest.find('=').ok_or_else(|| self.error("属性缺少 ="))?;
+            let name = rest[..eq].trim().to_string();
+            let value = rest[eq + 1..].trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '"' | '\''))
+                .ok_or_else(|| self.error(format!("属性 {} 的值缺少引号", name)))?;
+            let close = value[1..]
+                .find(quote)
+                .ok_or_else(|| self.error(format!("属性 {} 的值未闭合", name)))?;
+            attributes.push((name, self.decode(&value[1..1 + close])?));
+            rest = &value[close + 2..];
+        }
+    }
+
+    /// 把元素 `name` 的文本内容追加到 `out`，停在下一个标签之前；
+    /// 解码实体、保留 CDATA 原文并跳过注释
+    pub fn text(&mut self, name: &str, out: &mut String) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            let lt = rest
+                .find('<')
+                .ok_or_else(|| self.error(format!("缺少 </{}>", name)))?;
+            out.push_str(&self.decode(&rest[..lt])?);
+            self.pos += lt;
+            let rest = self.rest();
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata
+                    .find("]]>")
+                    .ok_or_else(|| self.error("CDATA 未闭合"))?;
+                out.push_str(&cdata[..end]);
+                self.pos += "<![CDATA[".len() + end + "]]>".len();
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn decode(&self, raw: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut rest = raw;
+        while let Some(amp) = rest.find('&') {
+            out.push_str(&rest[..amp]);
+            let semi = rest[amp..]
+                .find(';')
+                .ok_or_else(|| self.error("实体缺少 ;"))?;
+            let entity = &rest[amp + 1..amp + semi];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => entity.strip_prefix('#').and_then(|d| d.parse().ok()),
+                    };
+                    code.and_then(char::from_u32)
+                        .ok_or_else(|| self.error(format!("未知实体 &{};", entity)))?
+                }
+            };
+            out.push(c);
+            rest = &rest[amp + semi + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// 标签结束的 `>` 的位置，跳过引号内的属性值
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_tags_attributes_and_text() {
+        let mut xml = Reader::new(
+            "<?xml version=\"1.0\"?>\n<!-- c --><a x=\"1 &amp; 2\" y='b'>t&lt;<![CDATA[&]]><!-- c -->u<b/></a>",
+            "测试",
+        );
+        assert_eq!(xml.tag(), Ok(Tag::Open("a")));
+        assert_eq!(
+            xml.attributes().unwrap(),
+            [
+                ("x".to_string(), "1 & 2".to_string()),
+                ("y".to_string(), "b".to_string())
+            ]
+        );
+        let mut text = String::new();
+        xml.text("a", &mut text).unwrap();
+        assert_eq!(text, "t<&u");
+        assert_eq!(xml.tag(), Ok(Tag::Empty("b")));
+        assert!(xml.attributes().unwrap().is_empty());
+        xml.expect_close("a").unwrap();
+        xml.end().unwrap();
+    }
+
+    #[test]
+    fn quoted_attribute_values_may_contain_gt() {
+        let mut xml = Reader::new(r#"<a attr="a>b" other='>'>t</a>"#, "测试");
+        assert_eq!(xml.tag(), Ok(Tag::Open("a")));
+        assert_eq!(
+            xml.attributes().unwrap(),
+            [
+                ("attr".to_string(), "a>b".to_string()),
+                ("other".to_string(), ">".to_string())
+            ]
+        );
+        let mut text = String::new();
+        xml.text("a", &mut text).unwrap();
+        assert_eq!(text, "t");
+        xml.expect_close("a").unwrap();
+        assert!(Reader::new(r#"<a attr="a>"#, "").tag().is_err());
+    }
+
+    #[test]
+    fn errors_carry_context_and_line() {
+        let mut xml = Reader::new("\n\n<a>", "测试格式错误");
+        xml.tag().unwrap();
+        let err = xml.text("a", &mut String::new()).unwrap_err();
+        assert_eq!(err, "测试格式错误（第 3 行）: 缺少 </a>");
+        assert!(Reader::new("<a b=c>", "").tag().is_ok());
+        let mut xml = Reader::new("<a b=c>", "");
+        xml.tag().unwrap();
+        assert!(xml.attributes().is_err());
+        assert!(Reader::new("</>", "").tag().is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::config::{Config, SourceConfig};
+use crate::platform::command::Runner;
+use crate::platform::helper::DisplayItem;
+use crate::platform::{self, offline, SourceInfo};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 单个来源的扫描进度
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    Scanning,
+    /// 已完成，值为启动项数
+    Done(usize),
+    Failed(String),
+    Cancelled,
+}
+
+type Outcome = (&'static str, Result<Vec<DisplayItem>, String>);
+
+/// 一次后台扫描：每个启用的来源在独立线程中扫描，结果按来源陆续送回。
+/// 外部命令经共享取消标志的 `Runner` 执行，取消或丢弃扫描时会被结束。
+pub struct Scan {
+    /// 参与扫描的来源及其进度，按 `platform::SOURCES`（离线时为 `offline::SOURCES`）顺序
+    pub sources: Vec<(&'static SourceInfo, Progress)>,
+    rx: Receiver<Outcome>,
+    cancel: Arc<AtomicBool>,
+    started: Instant,
+}
+
+impl Scan {
+    /// 按配置扫描全部启用的来源；`offline` 为挂载的 Windows 卷时改为只读取其中的注册表 hive 与计划任务
+    pub fn start(config: &Config, offline: Option<&Path>) -> Scan {
+        let enabled = |sources: &'static [SourceInfo]| {
+            sources
+                .iter()
+                .map(|info| (info, config.source(info)))
+                .filter(|(_, source)| source.enabled == Some(true))
+                .collect()
+        };
+        match offline {
+            Some(root) => {
+                let root = root.to_path_buf();
+                Scan::spawn(
+                    enabled(offline::SOURCES),
+                    config.scan_timeout(),
+                    move |id, _, _| offline::scan_source(&root, id),
+                )
+            }
+            None => Scan::spawn(
+                enabled(platform::SOURCES),
+                config.scan_timeout(),
+                platform::scan_source,
+            ),
+        }
+    }
+
+    fn spawn<F>(
+        sources: Vec<(&'static SourceInfo, SourceConfig)>,
+        timeout: Duration,
+        scan: F,
+    ) -> Scan
+    where
+        F: Fn(&str, &SourceConfig, &Runner) -> Result<Vec<DisplayItem>, String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let scan = Arc::new(scan);
+        let progress = sources
+            .iter()
+            .map(|(info, _)| (*info, Progress::Scanning))
+            .collect();
+        for (info, source) in sources {
+            let tx = tx.clone();
+            let scan = scan.clone();
+            let runner = Runner::with_cancel(timeout, cancel.clone());
+            thread::spawn(move || {
+                let _ = tx.send((info.id, scan(info.id, &source, &runner)));
+            });
+        }
+        Scan {
+            sources: progress,
+            rx,
+            cancel,
+            started: Instant::now(),
+        }
+    }
+
+    /// 取出已完成来源的结果并更新进度；已取消的来源的结果被丢弃
+    pub fn poll(&mut self) -> Vec<(&'static str, Vec<DisplayItem>)> {
+        let mut finished = vec![];
+        while let Ok((id, result)) = self.rx.try_recv() {
+            let Some((_, progress)) = self.sources.iter_mut().find(|(info, _)| info.id == id)
+            else {
+                continue;
+            };
+            if *progress != Progress::Scanning {
+                continue;
+            }
+            match result {
+                Ok(items) => {
+                    *progress = Progress::Done(items.len());
+                    finished.push((id, items));
+                }
+                Err(e) => *progress = Progress::Failed(e),
+            }
+        }
+        finished
+    }
+
+    /// 取消仍在进行的来源：结束其外部命令，已完成的来源不受影响
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        for (_, progress) in &mut self.sources {
+            if *progress == Progress::Scanning {
+                *progress = Progress::Cancelled;
+            }
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.sources.iter().all(|(_, p)| *p != Progress::Scanning)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+impl Drop for Scan {
+    fn drop(&mut self) {
+        // 被新的扫描替换或退出程序时，不再等待仍在运行的外部命令
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static FAST: SourceInfo = SourceInfo {
+        id: "fast",
+        label: "Fast",
+        scans_dirs: false,
+        default_enabled: true,
+    };
+    static SLOW: SourceInfo = SourceInfo {
+        id: "slow",
+        label: "Slow",
+        scans_dirs: false,
+        default_enabled: true,
+    };
+    static BROKEN: SourceInfo = SourceInfo {
+        id: "broken",
+        label: "Broken",
+        scans_dirs: false,
+        default_enabled: true,
+    };
+
+    fn fake(id: &str, _: &SourceConfig, _: &Runner) -> Result<Vec<DisplayItem>, String> {
+        match id {
+            "fast" => Ok(vec![]),
+            "slow" => {
+                thread::sleep(Duration::from_millis(300));
+                Ok(vec![])
+            }
+            _ => Err("boom".to_string()),
+        }
+    }
+
+    fn wait_until(scan: &mut Scan, done: impl Fn(&Scan) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done(scan) && Instant::now() < deadline {
+            scan.poll();
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn progress(scan: &Scan, id: &str) -> Progress {
+        scan.sources
+            .iter()
+            .find(|(info, _)| info.id == id)
+            .map(|(_, p)| p.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn sources_finish_independently() {
+        let sources = vec![
+            (&FAST, SourceConfig::default()),
+            (&SLOW, SourceConfig::default()),
+            (&BROKEN, SourceConfig::default()),
+        ];
+        let mut scan = Scan::spawn(sources, Duration::from_secs(1), fake);
+        wait_until(&mut scan, |s| progress(s, "fast") != Progress::Scanning);
+        assert_eq!(progress(&scan, "fast"), Progress::Done(0));
+        wait_until(&mut scan, Scan::is_finished);
+        assert_eq!(progress(&scan, "slow"), Progress::Done(0));
+        assert_eq!(
+            progress(&scan, "broken"),
+            Progress::Failed("boom".to_string())
+        );
+    }
+
+    #[test]
+    fn cancel_discards_late_results() {
+        let sources = vec![(&SLOW, SourceConfig::default())];
+        let mut scan = Scan::spawn(sources, Duration::from_secs(1), fake);
+        scan.cancel();
+        assert!(scan.is_finished());
+        thread::sleep(Duration::from_millis(400));
+        assert!(scan.poll().is_empty());
+        assert_eq!(progress(&scan, "slow"), Progress::Cancelled);
+    }
+}
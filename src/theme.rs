@@ -0,0 +1,364 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// 界面配色与图标
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub header: Color,
+    pub type_label: Color,
+    pub label: Color,
+    pub path: Color,
+    /// 选中行的背景；为 `Color::Reset` 时改用反色显示
+    pub highlight_bg: Color,
+    pub highlight_fg: Color,
+    pub ok: Color,
+    pub error: Color,
+    pub info: Color,
+    pub help: Color,
+    /// 搜索命中部分
+    pub matched: Color,
+    pub icons: &'static Icons,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// 内置主题名称
+    pub const NAMES: &'static [&'static str] = &["dark", "light", "high-contrast"];
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "dark" | "default" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// 深色主题（默认）
+    pub fn dark() -> Theme {
+        Theme {
+            header: Color::Cyan,
+            type_label: Color::Yellow,
+            label: Color::White,
+            path: Color::DarkGray,
+            highlight_bg: Color::DarkGray,
+            highlight_fg: Color::Reset,
+            ok: Color::Green,
+            error: Color::Red,
+            info: Color::Gray,
+            help: Color::DarkGray,
+            matched: Color::LightRed,
+            icons: &Icons::EMOJI,
+        }
+    }
+
+    /// 浅色主题，适合浅色背景的终端
+    pub fn light() -> Theme {
+        Theme {
+            header: Color::Blue,
+            type_label: Color::Magenta,
+            label: Color::Black,
+            path: Color::DarkGray,
+            highlight_bg: Color::Gray,
+            highlight_fg: Color::Black,
+            ok: Color::Green,
+            error: Color::Red,
+            info: Color::DarkGray,
+            help: Color::DarkGray,
+            matched: Color::Red,
+            icons: &Icons::EMOJI,
+        }
+    }
+
+    /// 高对比度主题：只用亮色，选中行黄底黑字
+    pub fn high_contrast() -> Theme {
+        Theme {
+            header: Color::LightCyan,
+            type_label: Color::LightYellow,
+            label: Color::White,
+            path: Color::White,
+            highlight_bg: Color::Yellow,
+            highlight_fg: Color::Black,
+            ok: Color::LightGreen,
+            error: Color::LightRed,
+            info: Color::White,
+            help: Color::White,
+            matched: Color::LightMagenta,
+            icons: &Icons::EMOJI,
+        }
+    }
+
+    /// 无颜色（`NO_COLOR`）：全部使用终端默认色，选中行反色显示
+    pub fn no_color(self) -> Theme {
+        Theme {
+            header: Color::Reset,
+            type_label: Color::Reset,
+            label: Color::Reset,
+            path: Color::Reset,
+            highlight_bg: Color::Reset,
+            highlight_fg: Color::Reset,
+            ok: Color::Reset,
+            error: Color::Reset,
+            info: Color::Reset,
+            help: Color::Reset,
+            matched: Color::Reset,
+            ..self
+        }
+    }
+
+    /// 在 `base` 之上应用 `[themes.<name>]` 中的颜色；颜色无效时返回错误
+    pub fn custom(custom: &CustomTheme) -> Result<Theme, String> {
+        let base = custom.base.as_deref().unwrap_or("dark");
+        let mut theme = Theme::by_name(base).ok_or_else(|| {
+            format!(
+                "未知的基础主题 \"{}\"，可选: {}",
+                base,
+                Theme::NAMES.join(", ")
+            )
+        })?;
+        let slots = [
+            (&custom.header, &mut theme.header),
+            (&custom.type_label, &mut theme.type_label),
+            (&custom.label, &mut theme.label),
+            (&custom.path, &mut theme.path),
+            (&custom.highlight_bg, &mut theme.highlight_bg),
+            (&custom.highlight_fg, &mut theme.highlight_fg),
+            (&custom.ok, &mut theme.ok),
+            (&custom.error, &mut theme.error),
+            (&custom.info, &mut theme.info),
+            (&custom.help, &mut theme.help),
+            (&custom.matched, &mut theme.matched),
+        ];
+        for (value, slot) in slots {
+            if let Some(value) = value {
+                *slot = Color::from_str(value).map_err(|_| format!("无效的颜色: \"{}\"", value))?;
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn with_icons(self, icons: IconSet) -> Theme {
+        Theme {
+            icons: icons.icons(),
+            ..self
+        }
+    }
+
+    /// 选中行的样式
+    pub fn highlight(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        if self.highlight_bg == Color::Reset {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style.bg(self.highlight_bg).fg(self.highlight_fg)
+        }
+    }
+}
+
+/// 是否按 `NO_COLOR` 约定关闭颜色（变量存在且非空）
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// `[themes.<name>]`：自定义主题，未写的颜色取自 `base`。
+/// 颜色可写名称（`red`、`light-blue`）、`#rrggbb` 或 256 色序号
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomTheme {
+    /// 基础主题，缺省为 `dark`
+    pub base: Option<String>,
+    pub header: Option<String>,
+    pub type_label: Option<String>,
+    pub label: Option<String>,
+    pub path: Option<String>,
+    pub highlight_bg: Option<String>,
+    pub highlight_fg: Option<String>,
+    pub ok: Option<String>,
+    pub error: Option<String>,
+    pub info: Option<String>,
+    pub help: Option<String>,
+    pub matched: Option<String>,
+}
+
+/// 图标集合：emoji 在部分终端（如 Windows conhost、一些 SSH 客户端）中显示为方块或占两格，
+/// 可改用纯 ASCII
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    #[default]
+    Emoji,
+    Ascii,
+}
+
+impl IconSet {
+    pub fn icons(self) -> &'static Icons {
+        match self {
+            IconSet::Emoji => &Icons::EMOJI,
+            IconSet::Ascii => &Icons::ASCII,
+        }
+    }
+}
+
+/// 界面中使用的图标与符号
+#[derive(Debug)]
+pub struct Icons {
+    pub title: &'static str,
+    pub ok: &'static str,
+    pub error: &'static str,
+    pub warning: &'static str,
+    /// 选中行前的指示符
+    pub selected: &'static str,
+    pub marked: &'static str,
+    pub collapsed: &'static str,
+    pub expanded: &'static str,
+    pub enabled: &'static str,
+    pub disabled: &'static str,
+    pub broken: &'static str,
+    /// 扫描进度动画的各帧
+    pub spinner: &'static [&'static str],
+    /// 来源 ID → 图标
+    sources: &'static [(&'static str, &'static str)],
+    unknown_source: &'static str,
+}
+
+impl Icons {
+    pub const EMOJI: Icons = Icons {
+        title: "🔍",
+        ok: "✅",
+        error: "❌",
+        warning: "⚠ ",
+        selected: "▶ ",
+        marked: "✓",
+        collapsed: "▸",
+        expanded: "▾",
+        enabled: "●",
+        disabled: "○",
+        broken: "✗",
+        spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+        sources: &[
+            ("plist", "📝"),
+            ("login_item", "🚀"),
+            ("system_plist", "🍎"),
+            ("startup_items", "📦"),
+            ("login_hook", "🪝"),
+            ("periodic", "⏰"),
+            ("rc_common", "📜"),
+            ("launchd_conf", "📜"),
+            ("emond", "📡"),
+            ("app_bundle", "🧩"),
+            ("registry", "🔑"),
+            ("startup_folder", "📂"),
+            ("scheduled_task", "📅"),
+        ],
+        unknown_source: "•",
+    };
+
+    pub const ASCII: Icons = Icons {
+        title: "",
+        ok: "[ok]",
+        error: "[!!]",
+        warning: "!",
+        selected: "> ",
+        marked: "*",
+        collapsed: "+",
+        expanded: "-",
+        enabled: "+",
+        disabled: "-",
+        broken: "x",
+        spinner: &["|", "/", "-", "\\"],
+        sources: &[
+            ("plist", "P"),
+            ("login_item", "L"),
+            ("system_plist", "A"),
+            ("startup_items", "I"),
+            ("login_hook", "H"),
+            ("periodic", "C"),
+            ("rc_common", "E"),
+            ("launchd_conf", "E"),
+            ("emond", "M"),
+            ("app_bundle", "B"),
+            ("registry", "R"),
+            ("startup_folder", "S"),
+            ("scheduled_task", "T"),
+        ],
+        unknown_source: "?",
+    };
+
+    /// 来源对应的图标
+    pub fn source(&self, id: &str) -> &'static str {
+        self.sources
+            .iter()
+            .find(|(source, _)| *source == id)
+            .map_or(self.unknown_source, |(_, icon)| icon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_theme_overrides_base() {
+        let custom = CustomTheme {
+            base: Some("light".to_string()),
+            header: Some("#ff8800".to_string()),
+            ..CustomTheme::default()
+        };
+        let theme = Theme::custom(&custom).unwrap();
+        assert_eq!(theme.header, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.label, Theme::light().label);
+    }
+
+    #[test]
+    fn custom_theme_rejects_bad_values() {
+        let bad_color = CustomTheme {
+            ok: Some("not-a-color".to_string()),
+            ..CustomTheme::default()
+        };
+        assert!(Theme::custom(&bad_color).is_err());
+        let bad_base = CustomTheme {
+            base: Some("neon".to_string()),
+            ..CustomTheme::default()
+        };
+        assert!(Theme::custom(&bad_base).is_err());
+    }
+
+    #[test]
+    fn no_color_uses_reverse_highlight() {
+        let theme = Theme::high_contrast().no_color();
+        assert_eq!(theme.error, Color::Reset);
+        assert!(theme.highlight().add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn ascii_icons_are_ascii() {
+        let icons = IconSet::Ascii.icons();
+        let all = [
+            icons.title,
+            icons.ok,
+            icons.error,
+            icons.warning,
+            icons.selected,
+            icons.marked,
+            icons.collapsed,
+            icons.expanded,
+            icons.enabled,
+            icons.disabled,
+            icons.broken,
+            icons.unknown_source,
+        ];
+        assert!(all
+            .iter()
+            .chain(icons.spinner)
+            .chain(icons.sources.iter().map(|(_, icon)| icon))
+            .all(|s| s.is_ascii()));
+        assert_eq!(icons.source("registry"), "R");
+        assert_eq!(icons.source("nope"), "?");
+    }
+}
@@ -1,25 +1,47 @@
-use crate::platform::{self, helper::DisplayItem};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crate::config::{self, Config, Diagnostic};
+use crate::i18n::Lang;
+use crate::keymap::{Action, Keymap};
+use crate::layout::{Column, ViewLayout};
+use crate::logs::LogView;
+use crate::platform::command::Runner;
+use crate::platform::helper::{self, DisplayItem};
+use crate::platform::process::{self, ProcessInfo};
+use crate::platform::{self, EditTarget, Operation};
+use crate::scan::{Progress, Scan};
+use crate::theme::Theme;
+use crate::view::{self, Filter, Marks, Row, SortMode};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::widgets::{
+    Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row as TableRow, Table,
+    TableState, Tabs, Wrap,
+};
 use ratatui::Terminal;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
 use std::io;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
 
-/// 启动 TUI 应用，负责终端的初始化与还原
-pub fn run() -> io::Result<()> {
+/// 启动 TUI 应用，负责终端的初始化与还原；`offline` 为挂载的 Windows 卷时，
+/// 改为只读地扫描其中的注册表 hive 与计划任务
+pub fn run(offline: Option<PathBuf>) -> io::Result<()> {
     // 设置 panic hook：即便运行中 panic，也先还原终端状态，避免终端卡死在 raw mode
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
         original_hook(info);
     }));
 
@@ -29,38 +51,258 @@ pub fn run() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
+    let mut app = App::new(offline);
+    // 鼠标捕获会占用终端自身的文本选择，因此仅在配置开启时启用
+    if app.config.mouse {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
     let result = app.main_loop(&mut terminal);
 
     // 无论运行是否出错，都还原终端状态
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     result
 }
 
 struct App {
+    /// 扫描得到的全部启动项，按来源分段、来源按 `platform::SOURCES` 排列
     items: Vec<DisplayItem>,
+    /// 正在进行（或刚结束、尚未汇总）的后台扫描
+    scan: Option<Scan>,
+    /// 删除 / 禁用时执行外部命令，超时与扫描相同
+    runner: Runner,
+    /// 上次更新运行状态（进程快照）的时间
+    processes_checked: Instant,
+    /// 经过滤与排序后可见的项，元素为 `items` 的下标
+    visible: Vec<usize>,
+    /// 实际展示的行（分组时含分组标题）；`state` 的选中位置指向此列表
+    rows: Vec<Row>,
     state: ListState,
-    status: String,
+    /// 各来源标签页及其匹配项数
+    tabs: Vec<(String, usize)>,
+    /// 当前标签页的来源；`None` 为「全部」
+    tab: Option<String>,
+    /// 各标签页离开时的选中行与滚动位置
+    tab_states: BTreeMap<Option<String>, (Option<Selection>, usize)>,
+    status: Status,
     should_quit: bool,
-    confirm: bool,
+    /// 等待确认的操作
+    confirm: Option<Batch>,
+    /// 批量操作完成后的逐项结果
+    report: Option<Report>,
+    /// 按下编辑键后待打开的启动项；需要挂起终端，由主循环处理
+    pending_edit: Option<usize>,
+    /// 编辑后等待确认导入的 `.reg` 文件
+    import: Option<RegImport>,
+    /// 打开中的日志查看器
+    logs: Option<LogView>,
+    /// 确认 / 结果弹窗的滚动位置
+    popup_scroll: u16,
+    marks: Marks,
+    /// 列表 / 表格布局，修改后立即保存
+    layout: ViewLayout,
+    /// 列设置弹窗打开时为光标所在的列
+    column_editor: Option<usize>,
+    /// 在列表右侧显示选中项的详情
+    show_details: bool,
+    /// 上次绘制时列表所在区域，用于把鼠标点击换算为行
+    list_area: Rect,
+    /// 上次绘制时确认弹窗中 确认 / 取消 按钮的区域
+    confirm_buttons: Option<(Rect, Rect)>,
+    /// 上次单击的时间与行，用于识别双击
+    last_click: Option<(Instant, usize)>,
+    /// 正在输入搜索关键字（`/` 提示符）
+    searching: bool,
+    show_diagnostics: bool,
+    show_help: bool,
+    config: Config,
+    diagnostics: Vec<Diagnostic>,
+    keymap: Keymap,
+    lang: Lang,
+    theme: Theme,
+    sort: SortMode,
+    /// 按来源 / 目录分组展示
+    grouped: bool,
+    /// 只显示开机或登录时启动的项
+    startup_only: bool,
+    /// 已折叠的分组标识
+    collapsed: BTreeSet<String>,
+    filter: Filter,
+    quarantine: Option<PathBuf>,
+    /// 离线模式下挂载的 Windows 卷及其计算机名；结果只读，不与本机进程匹配
+    offline: Option<(PathBuf, Option<String>)>,
+}
+
+/// 状态栏消息；图标与颜色由级别和主题决定
+#[derive(Debug, Default)]
+struct Status {
+    level: Level,
+    text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Level {
+    #[default]
+    Info,
+    Ok,
+    Error,
+}
+
+impl Status {
+    fn info(text: String) -> Status {
+        Status {
+            level: Level::Info,
+            text,
+        }
+    }
+
+    fn ok(text: String) -> Status {
+        Status {
+            level: Level::Ok,
+            text,
+        }
+    }
+
+    fn error(text: String) -> Status {
+        Status {
+            level: Level::Error,
+            text,
+        }
+    }
+}
+
+/// 等待确认的操作及其目标（`items` 下标）
+struct Batch {
+    op: Operation,
+    targets: Vec<usize>,
+    /// 操作成功后结束目标正在运行的进程
+    terminate: bool,
+}
+
+/// 记录下来的选中行：启动项按删除令牌记录，`items` 下标在删除或重新扫描后会变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selection {
+    Item(String),
+    /// 分组标题的标识
+    Header(String),
+}
+
+/// 编辑后待导入的注册表文件
+struct RegImport {
+    path: PathBuf,
+    label: String,
+}
+
+/// 离开作用域时删除的临时文件
+struct TempFile(Option<PathBuf>);
+
+impl TempFile {
+    /// 保留文件，由调用方负责删除
+    fn keep(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// 批量操作的结果：每项的名称与失败原因
+struct Report {
+    op: Operation,
+    outcomes: Vec<(String, Option<String>)>,
 }
 
+/// 运行状态（进程快照）的刷新间隔
+const PROCESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 底部帮助栏展示的动作；完整列表见帮助弹窗
+const HELP_ACTIONS: &[Action] = &[
+    Action::Help,
+    Action::MoveDown,
+    Action::MoveUp,
+    Action::ToggleMark,
+    Action::Delete,
+    Action::Disable,
+    Action::Refresh,
+    Action::Search,
+    Action::Quit,
+];
+
 impl App {
-    fn new() -> Self {
-        let items = platform::get_display_items();
-        let mut state = ListState::default();
-        if !items.is_empty() {
-            state.select(Some(0));
-        }
-        Self {
-            status: format!("📦 共发现 {} 个开机启动项", items.len()),
-            items,
-            state,
+    fn new(offline: Option<PathBuf>) -> Self {
+        let (config, mut diagnostics) = config::load();
+        let (keymap, keymap_diagnostics) = config.keymap();
+        diagnostics.extend(keymap_diagnostics);
+
+        let lang = config.language;
+        let mut app = Self {
+            status: Status::default(),
+            items: vec![],
+            scan: Some(Scan::start(&config, offline.as_deref())),
+            runner: Runner::new(config.scan_timeout()),
+            processes_checked: Instant::now(),
+            visible: vec![],
+            rows: vec![],
+            state: ListState::default(),
+            tabs: vec![],
+            tab: None,
+            tab_states: BTreeMap::new(),
             should_quit: false,
-            confirm: false,
+            confirm: None,
+            report: None,
+            pending_edit: None,
+            import: None,
+            logs: None,
+            popup_scroll: 0,
+            marks: Marks::default(),
+            layout: ViewLayout::load(),
+            column_editor: None,
+            show_details: false,
+            list_area: Rect::default(),
+            confirm_buttons: None,
+            last_click: None,
+            searching: false,
+            show_diagnostics: false,
+            show_help: false,
+            keymap,
+            lang,
+            theme: config.theme(),
+            sort: config.view.sort,
+            grouped: config.view.grouped,
+            startup_only: config.view.startup_only,
+            collapsed: BTreeSet::new(),
+            filter: Filter::new(&config.view.filter, config.view.regex),
+            quarantine: config.quarantine_dir(),
+            diagnostics,
+            config,
+            offline: offline.map(|root| {
+                let name = platform::offline::computer_name(&root);
+                (root, name)
+            }),
+        };
+        app.apply_view();
+        // 配置问题不阻止启动，在状态栏提示，详情在诊断弹窗中查看
+        if let Some(d) = app.diagnostics.first() {
+            app.status = Status::error(tr!(
+                lang,
+                "配置错误 ({} 条，按 {} 查看): {}",
+                "Config error ({}, press {} for details): {}",
+                app.diagnostics.len(),
+                app.keymap.describe_keys(Action::Diagnostics),
+                d
+            ));
         }
+        app
     }
 
     fn main_loop<B: ratatui::backend::Backend>(
@@ -68,103 +310,1105 @@ impl App {
         terminal: &mut Terminal<B>,
     ) -> io::Result<()> {
         while !self.should_quit {
+            self.poll_scan();
+            if self.processes_checked.elapsed() >= PROCESS_INTERVAL {
+                self.update_running();
+            }
+            if let Some(view) = &mut self.logs {
+                view.poll();
+            }
             terminal.draw(|f| self.draw(f))?;
-            if !event::poll(Duration::from_millis(250))? {
+            // 扫描中缩短轮询间隔，让结果与进度动画及时刷新
+            let tick = if self.scan.is_some() { 100 } else { 250 };
+            if !event::poll(Duration::from_millis(tick))? {
                 continue;
             }
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    self.handle_key(key.code);
-                }
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key),
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                _ => {}
+            }
+            if let Some(i) = self.pending_edit.take() {
+                self.edit(terminal, i)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 挂起 TUI（与退出时相同地还原终端），在编辑器中打开启动项的定义，返回后恢复并校验
+    fn edit<B: ratatui::backend::Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        i: usize,
+    ) -> io::Result<()> {
+        let lang = self.lang;
+        let label = self.items[i].label.clone();
+        let target = match platform::edit_target(&self.items[i].option) {
+            Ok(target) => target,
+            Err(e) => {
+                self.status = Status::error(e);
+                return Ok(());
+            }
+        };
+        let path = target.path().to_path_buf();
+        // 导出的 .reg 含有启动项的内容，除非转交导入确认，否则在任何返回路径上删除
+        let mut export = TempFile(match &target {
+            EditTarget::RegExport(path) => Some(path.clone()),
+            EditTarget::File(_) => None,
+        });
+        let before = fs::read(&path).ok();
+
+        disable_raw_mode()?;
+        execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
+        let result = editor_command(&path).status();
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        if self.config.mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        terminal.clear()?;
+
+        match result {
+            Err(e) => {
+                self.status = Status::error(tr!(
+                    lang,
+                    "无法启动编辑器: {}",
+                    "Cannot start the editor: {}",
+                    e
+                ));
+                return Ok(());
+            }
+            Ok(status) if !status.success() => {
+                self.status = Status::error(tr!(
+                    lang,
+                    "编辑器异常退出: {}",
+                    "Editor exited with {}",
+                    status
+                ));
+                return Ok(());
+            }
+            Ok(_) => {}
+        }
+        if fs::read(&path).ok() == before {
+            self.status = Status::info(tr!(lang, "未修改: {}", "Not modified: {}", label));
+            return Ok(());
+        }
+        let validation = platform::validate_edit(&target, &self.runner);
+        match (target, validation) {
+            (EditTarget::RegExport(_), Err(e)) => {
+                self.status = Status::error(tr!(
+                    lang,
+                    "{} 格式有误，未导入: {}",
+                    "{} is invalid, not imported: {}",
+                    label,
+                    e
+                ));
+            }
+            (EditTarget::RegExport(path), Ok(())) => {
+                export.keep();
+                self.import = Some(RegImport { path, label });
+            }
+            (EditTarget::File(_), Err(e)) => {
+                self.refresh();
+                self.status = Status::error(tr!(
+                    lang,
+                    "已保存，但校验失败: {}",
+                    "Saved, but validation failed: {}",
+                    e.replace('\n', " ")
+                ));
+            }
+            (EditTarget::File(_), Ok(())) => {
+                self.refresh();
+                self.status = Status::ok(tr!(
+                    lang,
+                    "已保存并通过校验: {}",
+                    "Saved and valid: {}",
+                    label
+                ));
             }
         }
         Ok(())
     }
 
-    fn handle_key(&mut self, code: KeyCode) {
-        // 确认删除模式下优先处理
-        if self.confirm {
-            match code {
-                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-                    self.delete_selected();
+    fn handle_key(&mut self, key: KeyEvent) {
+        // 导入确认：确认键导入编辑后的 .reg 文件，其它键放弃；临时文件随后删除
+        if let Some(import) = self.import.take() {
+            let lang = self.lang;
+            if self.keymap.is(Action::Confirm, &key) {
+                self.status = match platform::import_reg(&import.path, &self.runner) {
+                    Ok(()) => {
+                        self.refresh();
+                        Status::ok(tr!(lang, "已导入: {}", "Imported: {}", import.label))
+                    }
+                    Err(e) => Status::error(tr!(lang, "导入失败: {}", "Import failed: {}", e)),
+                };
+            } else {
+                self.status = Status::info(tr!(
+                    lang,
+                    "已放弃导入: {}",
+                    "Import discarded: {}",
+                    import.label
+                ));
+            }
+            let _ = fs::remove_file(&import.path);
+            return;
+        }
+        // 确认模式下优先处理：确认键执行，上下移动键滚动目标列表，
+        // 有目标正在运行时切换是否结束进程，其它键取消
+        if let Some(mut batch) = self.confirm.take() {
+            if self.keymap.is(Action::Confirm, &key) {
+                self.run_batch(batch);
+            } else if self.scroll_popup(&key) {
+                self.confirm = Some(batch);
+            } else if self.keymap.is(Action::ToggleTerminate, &key) && self.any_running(&batch) {
+                batch.terminate = !batch.terminate;
+                self.confirm = Some(batch);
+            } else {
+                self.status = Status::info(tr!(
+                    self.lang,
+                    "已取消{}",
+                    "{} cancelled",
+                    batch.op.label(self.lang)
+                ));
+            }
+            return;
+        }
+        if self.logs.is_some() {
+            self.handle_log_key(key);
+            return;
+        }
+        // 结果报告：上下移动键滚动，其它键关闭
+        if self.report.is_some() {
+            if !self.scroll_popup(&key) {
+                self.report = None;
+            }
+            return;
+        }
+        if let Some(cursor) = self.column_editor {
+            self.handle_column_key(cursor, key);
+            return;
+        }
+        // 帮助弹窗：上下移动键滚动，其它键关闭
+        if self.show_help {
+            if !self.scroll_popup(&key) {
+                self.show_help = false;
+            }
+            return;
+        }
+        // 诊断弹窗打开时任意键关闭
+        if self.show_diagnostics {
+            self.show_diagnostics = false;
+            return;
+        }
+        if self.searching {
+            self.handle_search_key(key);
+            return;
+        }
+
+        match self.keymap.action_for(&key) {
+            Some(Action::Quit) => self.should_quit = true,
+            Some(Action::MoveDown) => self.next(),
+            Some(Action::MoveUp) => self.prev(),
+            Some(Action::MoveTop) => self.select_edge(false),
+            Some(Action::MoveBottom) => self.select_edge(true),
+            Some(Action::Delete) => self.request(Operation::Delete),
+            Some(Action::Disable) => self.request(Operation::Disable),
+            Some(Action::Open) => self.pending_edit = self.selected_index(),
+            Some(Action::Logs) => self.open_logs(),
+            Some(Action::ToggleMark) => self.toggle_mark(),
+            Some(Action::MarkAll) => {
+                self.marks.mark_all(&self.items, &self.visible);
+                self.status = Status::info(self.marks_summary());
+            }
+            Some(Action::InvertMarks) => {
+                self.marks.invert(&self.items, &self.visible);
+                self.status = Status::info(self.marks_summary());
+            }
+            Some(Action::Refresh) => self.refresh(),
+            Some(Action::ToggleLayout) => {
+                self.layout.table = !self.layout.table;
+                self.save_layout();
+            }
+            Some(Action::Columns) => self.column_editor = Some(0),
+            Some(Action::Details) => self.show_details = !self.show_details,
+            Some(Action::NextTab) => {
+                self.switch_tab((self.tab_index() + 1) % (self.tabs.len() + 1))
+            }
+            Some(Action::PrevTab) => {
+                let count = self.tabs.len() + 1;
+                self.switch_tab((self.tab_index() + count - 1) % count);
+            }
+            Some(Action::Search) => {
+                self.searching = true;
+                self.status = Status::info(self.search_summary());
+            }
+            Some(Action::CycleSort) => {
+                self.sort = self.sort.next();
+                self.apply_view();
+                self.status = Status::info(tr!(
+                    self.lang,
+                    "排序: {}",
+                    "Sort: {}",
+                    self.sort.label(self.lang)
+                ));
+            }
+            Some(Action::ToggleGroups) => {
+                self.grouped = !self.grouped;
+                self.apply_view();
+            }
+            Some(Action::ToggleStartupOnly) => {
+                self.startup_only = !self.startup_only;
+                self.apply_view();
+                self.status = Status::info(if self.startup_only {
+                    tr!(
+                        self.lang,
+                        "只显示开机 / 登录时启动的项（{} 个）",
+                        "Showing only items launched at boot or login ({})",
+                        self.visible.len()
+                    )
+                } else {
+                    self.lang
+                        .pick("显示全部启动项", "Showing all startup items")
+                        .to_string()
+                });
+            }
+            Some(Action::ToggleFold) => self.fold(None),
+            Some(Action::Collapse) => self.fold(Some(true)),
+            Some(Action::Expand) => self.fold(Some(false)),
+            Some(Action::NextMatch) => self.jump_match(true),
+            Some(Action::PrevMatch) => self.jump_match(false),
+            Some(Action::ClearFilter) if self.scan.is_some() => self.cancel_scan(),
+            // 没有可清除的过滤时，沿用 Esc 退出的习惯
+            Some(Action::ClearFilter) if self.filter.is_empty() => self.should_quit = true,
+            Some(Action::ClearFilter) => self.set_query(String::new()),
+            Some(Action::Diagnostics) => self.show_diagnostics = true,
+            Some(Action::Help) => {
+                self.popup_scroll = 0;
+                self.show_help = true;
+            }
+            Some(
+                Action::Confirm
+                | Action::ToggleTerminate
+                | Action::MoveColumnDown
+                | Action::MoveColumnUp,
+            ) => {}
+            // 未绑定的数字键切换标签页：1 为「全部」，其后依次为各来源
+            None => {
+                if let (KeyCode::Char(c @ '1'..='9'), KeyModifiers::NONE) =
+                    (key.code, key.modifiers)
+                {
+                    self.switch_tab(c as usize - '1' as usize);
+                }
+            }
+        }
+    }
+
+    /// 鼠标：单击选中行、双击打开详情、滚轮移动选中项；弹窗中可点击按钮或滚动
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let down = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left));
+        let scroll = match mouse.kind {
+            MouseEventKind::ScrollDown => Some(true),
+            MouseEventKind::ScrollUp => Some(false),
+            _ => None,
+        };
+        if self.confirm.is_some() {
+            if let (true, Some((confirm, cancel))) = (down, self.confirm_buttons) {
+                if contains(confirm, mouse.column, mouse.row) {
+                    if let Some(batch) = self.confirm.take() {
+                        self.run_batch(batch);
+                    }
+                } else if contains(cancel, mouse.column, mouse.row) {
+                    if let Some(batch) = self.confirm.take() {
+                        self.status = Status::info(tr!(
+                            self.lang,
+                            "已取消{}",
+                            "{} cancelled",
+                            batch.op.label(self.lang)
+                        ));
+                    }
                 }
-                _ => self.status = "已取消删除".to_string(),
             }
-            self.confirm = false;
+            self.scroll_popup_by(scroll);
             return;
         }
+        if let Some(view) = &mut self.logs {
+            match scroll {
+                Some(true) => view.scroll_by(3),
+                Some(false) => view.scroll_by(-3),
+                None => {}
+            }
+            return;
+        }
+        if self.report.is_some() || self.show_help {
+            if down {
+                self.report = None;
+                self.show_help = false;
+            }
+            self.scroll_popup_by(scroll);
+            return;
+        }
+        // 其它弹窗与搜索提示符只响应键盘
+        if self.column_editor.is_some() || self.show_diagnostics || self.searching {
+            return;
+        }
+        match scroll {
+            Some(true) => self.next(),
+            Some(false) => self.prev(),
+            None if down => {
+                let Some(row) = self.row_at(mouse.column, mouse.row) else {
+                    return;
+                };
+                self.state.select(Some(row));
+                let now = Instant::now();
+                let double = self.last_click.is_some_and(|(at, last)| {
+                    last == row && now.duration_since(at) < Duration::from_millis(400)
+                });
+                if double {
+                    self.show_details = true;
+                    self.last_click = None;
+                } else {
+                    self.last_click = Some((now, row));
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn scroll_popup_by(&mut self, scroll: Option<bool>) {
+        match scroll {
+            Some(true) => self.popup_scroll = self.popup_scroll.saturating_add(1),
+            Some(false) => self.popup_scroll = self.popup_scroll.saturating_sub(1),
+            None => {}
+        }
+    }
+
+    /// 终端坐标对应的行（`rows` 下标）：列表布局中启动项占两行、分组标题占一行，
+    /// 表格布局中每行一行且首行为表头
+    fn row_at(&self, x: u16, y: u16) -> Option<usize> {
+        let area = self.list_area;
+        let top = area.y + 1 + u16::from(self.layout.table);
+        if !contains(area, x, y) || y < top || y + 1 >= area.bottom() {
+            return None;
+        }
+        let mut line = top;
+        for (i, row) in self.rows.iter().enumerate().skip(self.state.offset()) {
+            let height = match row {
+                Row::Item(_) if !self.layout.table => 2,
+                _ => 1,
+            };
+            if y < line + height {
+                return Some(i);
+            }
+            line += height;
+        }
+        None
+    }
+
+    /// 当前标签页的序号：0 为「全部」
+    fn tab_index(&self) -> usize {
+        self.tab
+            .as_ref()
+            .and_then(|tab| self.tabs.iter().position(|(s, _)| s == tab))
+            .map_or(0, |i| i + 1)
+    }
+
+    /// 切换到第 `index` 个标签页，保存当前标签页的选中行与滚动位置并恢复目标标签页的
+    fn switch_tab(&mut self, index: usize) {
+        let tab = match index {
+            0 => None,
+            i => match self.tabs.get(i - 1) {
+                Some((source, _)) => Some(source.clone()),
+                None => return,
+            },
+        };
+        if tab == self.tab {
+            return;
+        }
+        let current = (self.selection(), self.state.offset());
+        self.tab_states.insert(self.tab.take(), current);
+        self.tab = tab;
+        let (row, offset) = self.tab_states.get(&self.tab).cloned().unwrap_or_default();
+        self.state = ListState::default().with_offset(offset);
+        self.rebuild(row);
+    }
 
-        match code {
-            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
-            KeyCode::Down | KeyCode::Char('j') => self.next(),
-            KeyCode::Up | KeyCode::Char('k') => self.prev(),
-            KeyCode::Char('d') | KeyCode::Delete => {
-                if self.state.selected().is_some() {
-                    self.confirm = true;
+    /// 打开选中项的日志查看器：定义中声明的日志文件，加上 `[logs]` 中为其配置的文件
+    fn open_logs(&mut self) {
+        let lang = self.lang;
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let item = &self.items[i];
+        let mut files = match platform::log_files(&item.option, &self.runner) {
+            Ok(files) => files,
+            Err(e) => {
+                self.status = Status::error(e);
+                return;
+            }
+        };
+        for path in self.config.log_files(&item.label) {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        if files.is_empty() {
+            self.status = Status::info(tr!(
+                lang,
+                "{} 没有声明日志文件（可在配置的 [logs] 中指定）",
+                "{} declares no log files (add them under [logs] in the config)",
+                item.label
+            ));
+            return;
+        }
+        self.logs = Some(LogView::open(item.label.clone(), files));
+    }
+
+    /// 日志查看器中的按键：上下移动键与翻页键滚动，首项 / 末项键跳到开头 / 恢复跟随，
+    /// 搜索键输入关键字（文本输入同列表搜索），`n`/`N` 跳转匹配；
+    /// 清除过滤键先清除关键字再关闭，退出键与日志键直接关闭
+    fn handle_log_key(&mut self, key: KeyEvent) {
+        let lang = self.lang;
+        let Some(view) = &mut self.logs else {
+            return;
+        };
+        if view.searching {
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            let mut query = view.filter.query().to_string();
+            match key.code {
+                KeyCode::Esc => {
+                    view.searching = false;
+                    view.filter.set_query(String::new());
+                }
+                KeyCode::Enter => {
+                    view.searching = false;
+                    view.jump_match(true);
                 }
+                KeyCode::Backspace => {
+                    query.pop();
+                    view.filter.set_query(query);
+                }
+                KeyCode::Char('r') if ctrl => view.filter.toggle_regex(),
+                KeyCode::Char('u') if ctrl => view.filter.set_query(String::new()),
+                KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                    query.push(c);
+                    view.filter.set_query(query);
+                }
+                _ => {}
+            }
+            return;
+        }
+        let page = view.height.max(1) as isize;
+        let keymap = &self.keymap;
+        if keymap.is(Action::MoveDown, &key) {
+            view.scroll_by(1);
+        } else if keymap.is(Action::MoveUp, &key) {
+            view.scroll_by(-1);
+        } else if key.code == KeyCode::PageDown {
+            view.scroll_by(page);
+        } else if key.code == KeyCode::PageUp {
+            view.scroll_by(-page);
+        } else if keymap.is(Action::MoveTop, &key) {
+            view.scroll = Some(0);
+        } else if keymap.is(Action::MoveBottom, &key) {
+            view.scroll = None;
+        } else if keymap.is(Action::Search, &key) {
+            view.searching = true;
+        } else if keymap.is(Action::NextMatch, &key) || keymap.is(Action::PrevMatch, &key) {
+            if !view.jump_match(keymap.is(Action::NextMatch, &key)) {
+                self.status = Status::info(tr!(lang, "日志中没有匹配项", "No matches in the log"));
+            }
+        } else if keymap.is(Action::ClearFilter, &key) && !view.filter.is_empty() {
+            view.filter.set_query(String::new());
+        } else if keymap.is(Action::ClearFilter, &key)
+            || keymap.is(Action::Quit, &key)
+            || keymap.is(Action::Logs, &key)
+        {
+            self.logs = None;
+        }
+    }
+
+    /// 列设置弹窗中的按键：上下移动键选择列，标记键显示 / 隐藏，折叠 / 展开键调整宽度，
+    /// `move_column_down` / `move_column_up` 调整列顺序，其它键关闭
+    fn handle_column_key(&mut self, cursor: usize, key: KeyEvent) {
+        let last = self.layout.columns.len() - 1;
+        let cursor = if self.keymap.is(Action::MoveDown, &key) {
+            (cursor + 1).min(last)
+        } else if self.keymap.is(Action::MoveUp, &key) {
+            cursor.saturating_sub(1)
+        } else if self.keymap.is(Action::ToggleMark, &key) {
+            if !self.layout.toggle(cursor) {
+                self.status = Status::error(
+                    self.lang
+                        .pick("至少保留一列", "At least one column must stay visible")
+                        .to_string(),
+                );
+            }
+            cursor
+        } else if self.keymap.is(Action::Collapse, &key) {
+            self.layout.resize(cursor, -2);
+            cursor
+        } else if self.keymap.is(Action::Expand, &key) {
+            self.layout.resize(cursor, 2);
+            cursor
+        } else if self.keymap.is(Action::MoveColumnDown, &key) {
+            self.layout.shift(cursor, true)
+        } else if self.keymap.is(Action::MoveColumnUp, &key) {
+            self.layout.shift(cursor, false)
+        } else {
+            self.column_editor = None;
+            self.save_layout();
+            return;
+        };
+        self.column_editor = Some(cursor);
+    }
+
+    fn save_layout(&mut self) {
+        if let Err(e) = self.layout.save() {
+            self.status = Status::error(tr!(
+                self.lang,
+                "保存布局失败: {}",
+                "Failed to save layout: {}",
+                e
+            ));
+        }
+    }
+
+    /// 搜索提示符中的按键：编辑关键字并实时过滤。
+    /// 文本输入不走键位映射，`Enter` 完成、`Esc` 清除并退出、`Ctrl+R` 切换正则、`Ctrl+U` 清空。
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.searching = false;
+                self.set_query(String::new());
+            }
+            KeyCode::Enter => {
+                self.searching = false;
+                self.status = Status::info(self.search_summary());
+            }
+            KeyCode::Up => self.prev(),
+            KeyCode::Down => self.next(),
+            KeyCode::Backspace => {
+                let mut query = self.filter.query().to_string();
+                query.pop();
+                self.set_query(query);
+            }
+            KeyCode::Char('r') if ctrl => {
+                self.filter.toggle_regex();
+                self.apply_view();
+            }
+            KeyCode::Char('u') if ctrl => self.set_query(String::new()),
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                let mut query = self.filter.query().to_string();
+                query.push(c);
+                self.set_query(query);
             }
-            KeyCode::Char('r') => self.refresh(),
             _ => {}
         }
     }
 
+    fn set_query(&mut self, query: String) {
+        self.filter.set_query(query);
+        self.apply_view();
+        self.status = Status::info(self.search_summary());
+    }
+
+    fn search_summary(&self) -> String {
+        if self.filter.is_empty() {
+            return tr!(
+                self.lang,
+                "共 {} 个启动项",
+                "{} startup items",
+                self.items.len()
+            );
+        }
+        tr!(
+            self.lang,
+            "{} 个匹配 / 共 {} 个",
+            "{} matches of {}",
+            self.visible.len(),
+            self.items.len()
+        )
+    }
+
+    /// `n`/`N`：在匹配项之间循环跳转
+    fn jump_match(&mut self, forward: bool) {
+        if self.filter.is_empty() {
+            self.status = Status::info(tr!(
+                self.lang,
+                "没有搜索关键字，按 {} 开始搜索",
+                "No active search, press {} to search",
+                self.keymap.describe_keys(Action::Search)
+            ));
+            return;
+        }
+        // 跳过分组标题，只停在启动项上
+        for _ in 0..self.rows.len() {
+            if forward {
+                self.next();
+            } else {
+                self.prev();
+            }
+            if self.selected_index().is_some() {
+                break;
+            }
+        }
+        if let Some(i) = self.state.selected() {
+            let nth = self.rows[..=i]
+                .iter()
+                .filter(|r| matches!(r, Row::Item(_)))
+                .count();
+            self.status = Status::info(tr!(
+                self.lang,
+                "第 {}/{} 个匹配",
+                "Match {}/{}",
+                nth,
+                self.visible.len()
+            ));
+        }
+    }
+
+    /// 折叠 / 展开选中行所在的分组：`Some(true)` 折叠，`Some(false)` 展开，`None` 切换。
+    /// 选中启动项时作用于其所在目录分组，并把光标移到该分组标题上。
+    fn fold(&mut self, collapse: Option<bool>) {
+        if !self.grouped {
+            self.status = Status::info(tr!(
+                self.lang,
+                "未开启分组，按 {} 切换分组视图",
+                "Not grouped, press {} to group",
+                self.keymap.describe_keys(Action::ToggleGroups)
+            ));
+            return;
+        }
+        let Some(row) = self.state.selected().and_then(|i| self.rows.get(i)) else {
+            return;
+        };
+        let (key, is_collapsed, depth) = match row {
+            Row::Header {
+                key,
+                collapsed,
+                depth,
+                ..
+            } => (key.clone(), *collapsed, *depth),
+            Row::Item(i) => (view::group_keys(&self.items[*i]).1, false, 1),
+        };
+        let target = collapse.unwrap_or(!is_collapsed);
+        if target && is_collapsed && depth == 1 {
+            // 已折叠的目录分组再次折叠时，折叠其所属来源分组
+            if let Some((source, _)) = key.split_once('\n') {
+                self.collapsed.insert(source.to_string());
+                self.apply_view();
+                self.select_header(source);
+            }
+            return;
+        }
+        if target {
+            self.collapsed.insert(key.clone());
+        } else {
+            self.collapsed.remove(&key);
+        }
+        self.apply_view();
+        self.select_header(&key);
+    }
+
+    fn select_header(&mut self, key: &str) {
+        let pos = self
+            .rows
+            .iter()
+            .position(|r| matches!(r, Row::Header { key: k, .. } if k == key));
+        if pos.is_some() {
+            self.state.select(pos);
+        }
+    }
+
+    /// 当前选中项在 `items` 中的下标；选中分组标题时为 `None`
+    fn selected_index(&self) -> Option<usize> {
+        match self.state.selected().and_then(|i| self.rows.get(i)) {
+            Some(Row::Item(idx)) => Some(*idx),
+            _ => None,
+        }
+    }
+
+    /// 当前选中的行；`rows` 尚未随 `items` 更新时按下标取到的可能是其后的项
+    fn selection(&self) -> Option<Selection> {
+        match self.state.selected().and_then(|i| self.rows.get(i))? {
+            Row::Item(i) => self
+                .items
+                .get(*i)
+                .map(|item| Selection::Item(item.option.value.clone())),
+            Row::Header { key, .. } => Some(Selection::Header(key.clone())),
+        }
+    }
+
+    /// 重新计算可见项与行，并尽量保持选中同一行（启动项或分组标题）
+    fn apply_view(&mut self) {
+        self.rebuild(self.selection());
+    }
+
+    /// 重新计算标签页、可见项与行，选中 `selected` 所在的行（找不到时保持原位置）
+    fn rebuild(&mut self, selected: Option<Selection>) {
+        let mut matching = view::arrange(&self.items, self.sort, &self.filter);
+        if self.startup_only {
+            matching.retain(|&i| self.items[i].runs_at_startup());
+        }
+        self.tabs = view::tabs(&self.items, &matching);
+        // 来源已没有任何启动项（如刷新后）时回到「全部」
+        if self.tab_index() == 0 {
+            self.tab = None;
+        }
+        self.visible = match &self.tab {
+            Some(tab) => matching
+                .into_iter()
+                .filter(|&i| self.items[i].type_label == *tab)
+                .collect(),
+            None => matching,
+        };
+        self.rows = view::rows(&self.items, &self.visible, self.grouped, &self.collapsed);
+        let items = &self.items;
+        let same_row = |row: &Row| match (&selected, row) {
+            (Some(Selection::Item(token)), Row::Item(i)) => items[*i].option.value == *token,
+            (Some(Selection::Header(a)), Row::Header { key: b, .. }) => a == b,
+            _ => false,
+        };
+        let pos = self.rows.iter().position(same_row).or_else(|| {
+            let i = self.state.selected().unwrap_or(0);
+            (!self.rows.is_empty()).then(|| i.min(self.rows.len() - 1))
+        });
+        self.state.select(pos);
+    }
+
     fn next(&mut self) {
-        if self.items.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = self.state.selected().unwrap_or(0);
-        let i = (i + 1) % self.items.len();
+        let i = (i + 1) % self.rows.len();
         self.state.select(Some(i));
     }
 
     fn prev(&mut self) {
-        if self.items.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = self.state.selected().unwrap_or(0);
-        let i = if i == 0 { self.items.len() - 1 } else { i - 1 };
+        let i = if i == 0 { self.rows.len() - 1 } else { i - 1 };
         self.state.select(Some(i));
     }
 
+    fn select_edge(&mut self, last: bool) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let i = if last { self.rows.len() - 1 } else { 0 };
+        self.state.select(Some(i));
+    }
+
+    /// 重新扫描；进行中的扫描被取消。新结果到达前保留当前列表
     fn refresh(&mut self) {
-        self.items = platform::get_display_items();
-        if self.items.is_empty() {
-            self.state.select(None);
-        } else {
-            let i = self.state.selected().unwrap_or(0).min(self.items.len() - 1);
-            self.state.select(Some(i));
+        let offline = self.offline.as_ref().map(|(root, _)| root.as_path());
+        self.scan = Some(Scan::start(&self.config, offline));
+        self.status = Status::default();
+    }
+
+    fn cancel_scan(&mut self) {
+        if let Some(scan) = &mut self.scan {
+            scan.cancel();
+        }
+    }
+
+    /// 合并已完成来源的结果：替换该来源的旧项，保持选中项不变。
+    /// 确认弹窗打开时暂缓合并，避免其目标下标失效。
+    fn poll_scan(&mut self) {
+        if self.confirm.is_some() {
+            return;
+        }
+        let Some(scan) = &mut self.scan else {
+            return;
+        };
+        let finished = scan.poll();
+        let done = scan.is_finished();
+        if !finished.is_empty() {
+            let selected = self.selection();
+            let order = |id: &str| platform::SOURCES.iter().position(|s| s.id == id);
+            for (id, items) in finished {
+                self.items.retain(|item| item.source != id);
+                let at = self
+                    .items
+                    .iter()
+                    .position(|item| order(item.source) > order(id))
+                    .unwrap_or(self.items.len());
+                self.items.splice(at..at, items);
+            }
+            self.update_running();
+            self.rebuild(selected);
+        }
+        if done {
+            if let Some(scan) = self.scan.take() {
+                self.finish_scan(&scan);
+            }
+        }
+    }
+
+    /// 按最新的进程快照更新各启动项的运行状态；离线扫描的启动项不在本机运行
+    fn update_running(&mut self) {
+        if self.offline.is_some() {
+            return;
+        }
+        let processes = process::snapshot();
+        for item in &mut self.items {
+            item.running = find_process(&processes, item).cloned();
+        }
+        self.processes_checked = Instant::now();
+    }
+
+    fn any_running(&self, batch: &Batch) -> bool {
+        batch
+            .targets
+            .iter()
+            .any(|&i| self.items[i].running.is_some())
+    }
+
+    /// 扫描结束后清理失效的标记，并在状态栏汇总结果
+    fn finish_scan(&mut self, scan: &Scan) {
+        let lang = self.lang;
+        self.marks.retain_existing(&self.items);
+        self.apply_view();
+        let failed: Vec<_> = scan
+            .sources
+            .iter()
+            .filter_map(|(info, p)| match p {
+                Progress::Failed(e) => Some((info.label, e)),
+                _ => None,
+            })
+            .collect();
+        let cancelled = scan.sources.iter().any(|(_, p)| *p == Progress::Cancelled);
+        if let Some((label, e)) = failed.first() {
+            self.status = Status::error(tr!(
+                lang,
+                "{} 个来源扫描失败（{}: {}），共 {} 个启动项",
+                "{} source(s) failed ({}: {}), {} startup items",
+                failed.len(),
+                label,
+                e,
+                self.items.len()
+            ));
+        } else if cancelled {
+            self.status = Status::info(tr!(
+                lang,
+                "已取消扫描，未完成的来源保留上次结果，共 {} 个启动项",
+                "Scan cancelled, unfinished sources keep earlier results, {} startup items",
+                self.items.len()
+            ));
+        } else if self.status.text.is_empty() {
+            // 状态栏有扫描期间的提示（如配置错误）时保留
+            self.status = Status::info(tr!(
+                lang,
+                "共发现 {} 个开机启动项（{:.1} 秒）",
+                "Found {} startup items ({:.1}s)",
+                self.items.len(),
+                scan.elapsed().as_secs_f32()
+            ));
         }
-        self.status = format!("🔄 已刷新，共 {} 个启动项", self.items.len());
     }
 
-    fn delete_selected(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => i,
+    /// 扫描进度：动画帧与各来源状态，如 `⠋ Plist 12 · Login Item ...`
+    fn scan_progress(&self, scan: &Scan) -> String {
+        let spinner = self.theme.icons.spinner;
+        let frame = spinner[(scan.elapsed().as_millis() / 100) as usize % spinner.len()];
+        let sources: Vec<String> = scan
+            .sources
+            .iter()
+            .map(|(info, p)| match p {
+                Progress::Scanning => format!("{} ...", info.label),
+                Progress::Done(n) => format!("{} {}", info.label, n),
+                Progress::Failed(_) => format!("{} {}", info.label, self.theme.icons.error),
+                Progress::Cancelled => format!("{} -", info.label),
+            })
+            .collect();
+        tr!(
+            self.lang,
+            "{} 扫描中 {}（{} 取消）",
+            "{} Scanning {} ({} to cancel)",
+            frame,
+            sources.join(" · "),
+            self.keymap.describe_keys(Action::ClearFilter)
+        )
+    }
+
+    /// 标记 / 取消标记选中项并下移；选中分组标题时作用于整组可见项
+    fn toggle_mark(&mut self) {
+        match self.state.selected().and_then(|i| self.rows.get(i)) {
+            Some(Row::Item(i)) => {
+                self.marks.toggle(&self.items[*i]);
+                self.next();
+            }
+            Some(Row::Header { key, .. }) => {
+                let members: Vec<usize> = self
+                    .visible
+                    .iter()
+                    .copied()
+                    .filter(|&i| {
+                        let (source, dir) = view::group_keys(&self.items[i]);
+                        *key == source || *key == dir
+                    })
+                    .collect();
+                if members.iter().all(|&i| self.marks.contains(&self.items[i])) {
+                    for &i in &members {
+                        self.marks.unmark(&self.items[i]);
+                    }
+                } else {
+                    self.marks.mark_all(&self.items, &members);
+                }
+            }
             None => return,
+        }
+        self.status = Status::info(self.marks_summary());
+    }
+
+    fn marks_summary(&self) -> String {
+        tr!(self.lang, "已标记 {} 项", "{} marked", self.marks.len())
+    }
+
+    /// 请求执行操作：有标记时作用于全部标记项，否则作用于选中项；执行前需确认
+    fn request(&mut self, op: Operation) {
+        let targets = if self.marks.is_empty() {
+            self.selected_index().into_iter().collect()
+        } else {
+            self.marks.targets(&self.items)
         };
-        if i >= self.items.len() {
+        if targets.is_empty() {
             return;
         }
-        let label = self.items[i].label.clone();
-        let result = platform::delete_item(&self.items[i].option);
-        match result {
-            Ok(()) => {
-                self.status = format!("✅ 已删除: {}", label);
+        self.popup_scroll = 0;
+        self.confirm = Some(Batch {
+            op,
+            targets,
+            terminate: self.config.delete.terminate,
+        });
+    }
+
+    /// 弹窗中的上下移动键用于滚动，返回是否已处理
+    fn scroll_popup(&mut self, key: &KeyEvent) -> bool {
+        if self.keymap.is(Action::MoveDown, key) {
+            self.popup_scroll = self.popup_scroll.saturating_add(1);
+        } else if self.keymap.is(Action::MoveUp, key) {
+            self.popup_scroll = self.popup_scroll.saturating_sub(1);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// 逐项执行操作；单项失败不影响其余项。成功的项取消标记，失败的项保持标记以便重试。
+    /// 要求结束进程时，操作成功后再结束该项正在运行的进程，结束失败记为该项的错误；
+    /// 缓存的运行状态可能已过时，结束前按新的进程快照重新匹配。
+    fn run_batch(&mut self, batch: Batch) {
+        let Batch {
+            op,
+            targets,
+            terminate,
+        } = batch;
+        let lang = self.lang;
+        let mut outcomes = vec![];
+        let mut done = vec![];
+        let mut terminated = vec![];
+        let processes = if terminate {
+            process::snapshot()
+        } else {
+            vec![]
+        };
+        for &i in &targets {
+            let item = &self.items[i];
+            let mut error = op
+                .apply(&item.option, self.quarantine.as_deref(), &self.runner)
+                .err()
+                .map(|e| e.to_string());
+            if error.is_none() {
+                done.push(i);
+                if let Some(running) = find_process(&processes, item) {
+                    match process::terminate(running.pid, &self.runner) {
+                        Ok(()) => terminated.push((i, running.pid)),
+                        Err(e) => error = Some(e),
+                    }
+                }
+            }
+            outcomes.push((item.label.clone(), error));
+        }
+        for &i in &done {
+            let item = &mut self.items[i];
+            self.marks.unmark(item);
+            if op == Operation::Disable {
+                item.disabled = true;
+            }
+            if terminated.iter().any(|&(t, _)| t == i) {
+                item.running = None;
+            }
+        }
+        if op == Operation::Delete {
+            // targets 按下标升序，倒序移除不影响其余下标
+            for &i in done.iter().rev() {
                 self.items.remove(i);
-                if self.items.is_empty() {
-                    self.state.select(None);
-                } else {
-                    let i = i.min(self.items.len() - 1);
-                    self.state.select(Some(i));
+            }
+        }
+        self.apply_view();
+
+        if let [(label, error)] = outcomes.as_slice() {
+            self.status = match (error, op, &self.quarantine) {
+                (Some(e), _, _) => Status::error(tr!(
+                    lang,
+                    "{}失败: {}",
+                    "Failed to {}: {}",
+                    op.label(lang),
+                    e
+                )),
+                (None, Operation::Delete, Some(dir)) => Status::ok(tr!(
+                    lang,
+                    "已删除: {}（已隔离到 {}）",
+                    "Deleted: {} (quarantined in {})",
+                    label,
+                    dir.display()
+                )),
+                (None, Operation::Delete, None) => {
+                    Status::ok(tr!(lang, "已删除: {}", "Deleted: {}", label))
+                }
+                (None, Operation::Disable, _) => {
+                    Status::ok(tr!(lang, "已禁用: {}", "Disabled: {}", label))
                 }
+            };
+            if let [(_, pid)] = terminated.as_slice() {
+                self.status.text += &tr!(lang, "，已结束进程 {}", ", terminated PID {}", pid);
             }
-            Err(e) => self.status = format!("❌ 删除失败: {}", e),
+            return;
         }
+        let failed = outcomes.iter().filter(|(_, e)| e.is_some()).count();
+        self.status = if failed == 0 {
+            Status::ok(tr!(
+                lang,
+                "已{} {} 项",
+                "{}: {} succeeded",
+                op.label(lang),
+                done.len()
+            ))
+        } else {
+            Status::error(tr!(
+                lang,
+                "{}: {} 项成功，{} 项失败（失败项保持标记）",
+                "{}: {} succeeded, {} failed (failed items stay marked)",
+                op.label(lang),
+                outcomes.len() - failed,
+                failed
+            ))
+        };
+        self.popup_scroll = 0;
+        self.report = Some(Report { op, outcomes });
     }
 
     fn draw(&mut self, f: &mut ratatui::Frame) {
+        let theme = &self.theme;
+        let lang = self.lang;
         let chunks = Layout::vertical([
             Constraint::Length(3),
+            Constraint::Length(1),
             Constraint::Min(1),
             Constraint::Length(1),
             Constraint::Length(1),
@@ -172,98 +1416,1166 @@ impl App {
         .split(f.size());
 
         // 标题栏
-        let header = Paragraph::new("BootWatch 🔍  开机启动项管理")
+        let mut title = tr!(
+            lang,
+            "BootWatch {}  开机启动项管理",
+            "BootWatch {}  Startup item manager",
+            theme.icons.title
+        );
+        if let Some((root, name)) = &self.offline {
+            title += &offline_title(root, name.as_deref(), lang);
+        }
+        let header = Paragraph::new(title)
             .style(
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.header)
                     .add_modifier(Modifier::BOLD),
             )
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(header, chunks[0]);
 
-        // 启动项列表
+        // 来源标签页
+        let all: usize = self.tabs.iter().map(|(_, n)| n).sum();
+        let titles = std::iter::once(tr!(lang, "全部 ({})", "All ({})", all)).chain(
+            self.tabs
+                .iter()
+                .map(|(source, n)| format!("{} ({})", source, n)),
+        );
+        let tabs = Tabs::new(titles)
+            .select(self.tab_index())
+            .style(Style::default().fg(theme.help))
+            .highlight_style(
+                Style::default()
+                    .fg(theme.header)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            );
+        f.render_widget(tabs, chunks[1]);
+
+        let title = self.list_title();
+        let (list_area, details_area) = if self.show_details {
+            let split =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .split(chunks[2]);
+            (split[0], Some(split[1]))
+        } else {
+            (chunks[2], None)
+        };
+        self.list_area = list_area;
+        if self.layout.table {
+            self.draw_table(f, list_area, title);
+        } else {
+            self.draw_list(f, list_area, title);
+        }
+        if let Some(area) = details_area {
+            self.draw_details(f, area);
+        }
+        let theme = &self.theme;
+
+        // 状态栏；搜索时替换为输入提示符
+        if self.searching {
+            f.render_widget(self.search_prompt(), chunks[3]);
+        } else {
+            let (icon, status_color) = match self.status.level {
+                Level::Info => ("", theme.info),
+                Level::Ok => (theme.icons.ok, theme.ok),
+                Level::Error => (theme.icons.error, theme.error),
+            };
+            let mut spans = vec![];
+            if let Some(scan) = &self.scan {
+                spans.push(Span::styled(
+                    self.scan_progress(scan),
+                    Style::default().fg(theme.info),
+                ));
+                spans.push(Span::raw("  "));
+            }
+            if !icon.is_empty() {
+                spans.push(Span::styled(
+                    format!("{} ", icon),
+                    Style::default().fg(status_color),
+                ));
+            }
+            spans.push(Span::styled(
+                self.status.text.as_str(),
+                Style::default().fg(status_color),
+            ));
+            f.render_widget(Paragraph::new(Line::from(spans)), chunks[3]);
+        }
+
+        if let Some(mut view) = self.logs.take() {
+            self.draw_logs(f, chunks[2], &mut view);
+            self.logs = Some(view);
+        }
+        let theme = &self.theme;
+
+        // 帮助栏
+        let help = self.keymap.help_line(HELP_ACTIONS, lang);
+        let help = Paragraph::new(help).style(Style::default().fg(theme.help));
+        f.render_widget(help, chunks[4]);
+
+        self.confirm_buttons = self
+            .confirm
+            .as_ref()
+            .map(|batch| self.draw_confirm(f, batch));
+        if let Some(report) = &self.report {
+            self.draw_report(f, report);
+        }
+        if let Some(import) = &self.import {
+            self.draw_import(f, import);
+        }
+        if let Some(cursor) = self.column_editor {
+            self.draw_columns(f, cursor);
+        }
+        if self.show_help {
+            // 把滚动位置收敛到可滚动范围内，避免滚过头后需要多按几次才能回滚
+            self.popup_scroll = self.popup_scroll.min(self.draw_help(f));
+        }
+        if self.show_diagnostics {
+            self.draw_diagnostics(f);
+        }
+    }
+
+    /// 列表标题：过滤生效时显示 匹配数/总数，并附带标记数与排序方式
+    fn list_title(&self) -> String {
+        let lang = self.lang;
+        let mut title = if self.filter.is_empty() {
+            tr!(
+                lang,
+                "启动项 ({})",
+                "Startup items ({})",
+                self.visible.len()
+            )
+        } else {
+            tr!(
+                lang,
+                "启动项 ({}/{}) · /{}",
+                "Startup items ({}/{}) · /{}",
+                self.visible.len(),
+                self.items.len(),
+                self.filter.query()
+            )
+        };
+        if self.startup_only {
+            title.push_str(lang.pick(" · 仅开机启动", " · startup only"));
+        }
+        if !self.marks.is_empty() {
+            title.push_str(&tr!(lang, " · 已标记 {}", " · {} marked", self.marks.len()));
+        }
+        if self.sort != SortMode::Scan {
+            title.push_str(&tr!(
+                lang,
+                " · 排序: {}",
+                " · sort: {}",
+                self.sort.label(lang)
+            ));
+        }
+        title
+    }
+
+    /// 两行列表布局：第一行图标 / 类型 / 名称，第二行路径
+    fn draw_list(&mut self, f: &mut ratatui::Frame, area: Rect, title: String) {
+        let theme = &self.theme;
+        let lang = self.lang;
+        // 启动项列表，命中搜索的部分高亮
+        let matched = Style::default()
+            .fg(theme.matched)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let indent = if self.grouped { "    " } else { "" };
         let list_items: Vec<ListItem> = self
-            .items
+            .rows
             .iter()
-            .map(|it| {
-                let main = Line::from(vec![
-                    Span::styled(format!("{} ", it.icon), Style::default()),
+            .map(|row| {
+                let i = match row {
+                    Row::Header { .. } => return ListItem::new(self.group_header(row)),
+                    Row::Item(i) => *i,
+                };
+                let it = &self.items[i];
+                let mark = self.mark_of(it);
+                let mut main = vec![
+                    Span::raw(indent),
+                    Span::styled(
+                        mark,
+                        Style::default().fg(theme.ok).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("{} ", theme.icons.source(it.source))),
                     Span::styled(
                         format!("[{}] ", it.type_label),
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(theme.type_label),
                     ),
-                    Span::styled(it.label.clone(), Style::default().fg(Color::White)),
-                ]);
-                let path = it.path.clone().unwrap_or_else(|| "-".to_string());
-                let sub = Line::from(Span::styled(
-                    format!("    {}", path),
-                    Style::default().fg(Color::DarkGray),
+                ];
+                if it.broken {
+                    main.push(Span::styled(
+                        lang.pick("[失效] ", "[broken] "),
+                        Style::default().fg(theme.error),
+                    ));
+                }
+                if it.protected {
+                    main.push(Span::styled(
+                        lang.pick("[受保护] ", "[protected] "),
+                        Style::default().fg(theme.info),
+                    ));
+                }
+                // 任务管理器看不到的自启动位置（RunOnce 等）标出所在键
+                if it.unlisted {
+                    let badge = match &it.kind {
+                        Some(kind) => tr!(
+                            lang,
+                            "[{}，任务管理器不显示] ",
+                            "[{}, not in Task Manager] ",
+                            kind
+                        ),
+                        None => lang
+                            .pick("[任务管理器不显示] ", "[not in Task Manager] ")
+                            .to_string(),
+                    };
+                    main.push(Span::styled(badge, Style::default().fg(theme.info)));
+                }
+                if let Some(p) = &it.running {
+                    main.push(Span::styled(
+                        format!("[{}] ", running_badge(p, lang)),
+                        Style::default().fg(theme.ok),
+                    ));
+                }
+                // 不在开机 / 登录时启动的 launchd 任务标出实际运行时机
+                if let (false, Some(summary)) = (it.runs_at_startup(), it.trigger_summary(lang)) {
+                    main.push(Span::styled(
+                        format!("[{}] ", summary),
+                        Style::default().fg(theme.info),
+                    ));
+                }
+                main.extend(highlight(
+                    &it.label,
+                    &self.filter,
+                    Style::default().fg(theme.label),
+                    matched,
+                ));
+                let path = it.path.as_deref().unwrap_or("-");
+                let mut sub = vec![Span::raw(format!("{}    ", indent))];
+                sub.extend(highlight(
+                    path,
+                    &self.filter,
+                    Style::default().fg(theme.path),
+                    matched,
+                ));
+                sub.push(Span::styled(
+                    format!("  ({})", it.scope.label(lang)),
+                    Style::default().fg(theme.path),
                 ));
-                ListItem::new(vec![main, sub])
+                // 失效项提示缺失的可执行文件
+                if let (true, Some(target)) = (it.broken, &it.target) {
+                    sub.push(Span::styled(
+                        tr!(lang, "  缺失: {}", "  missing: {}", target),
+                        Style::default().fg(theme.error),
+                    ));
+                }
+                ListItem::new(vec![Line::from(main), Line::from(sub)])
             })
             .collect();
 
         let list = List::new(list_items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("启动项 ({})", self.items.len())),
-            )
-            .highlight_style(
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("▶ ");
-        f.render_stateful_widget(list, chunks[1], &mut self.state);
-
-        // 状态栏
-        let status_color = if self.status.starts_with("❌") {
-            Color::Red
-        } else if self.status.starts_with("✅") {
-            Color::Green
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(theme.highlight())
+            .highlight_symbol(theme.icons.selected);
+        f.render_stateful_widget(list, area, &mut self.state);
+    }
+
+    /// 表格布局：每项一行，列与列宽由 `layout` 决定
+    fn draw_table(&mut self, f: &mut ratatui::Frame, area: Rect, title: String) {
+        let theme = &self.theme;
+        let matched = Style::default()
+            .fg(theme.matched)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let columns: Vec<Column> = self.layout.visible().map(|c| c.column).collect();
+        // 最后一列占满剩余宽度
+        let widths: Vec<Constraint> = self
+            .layout
+            .visible()
+            .enumerate()
+            .map(|(i, c)| {
+                if i + 1 == columns.len() {
+                    Constraint::Min(c.width)
+                } else {
+                    Constraint::Length(c.width)
+                }
+            })
+            .collect();
+        let header = TableRow::new(columns.iter().map(|c| Cell::from(c.title(self.lang)))).style(
+            Style::default()
+                .fg(theme.header)
+                .add_modifier(Modifier::BOLD),
+        );
+        // 分组标题放在名称列（隐藏时放在第一列）
+        let title_col = columns.iter().position(|&c| c == Column::Name).unwrap_or(0);
+        let rows: Vec<TableRow> = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                Row::Header { .. } => {
+                    let mut cells = vec![Cell::from(""); columns.len()];
+                    cells[title_col] = Cell::from(self.group_header(row));
+                    TableRow::new(cells)
+                }
+                Row::Item(i) => TableRow::new(
+                    columns
+                        .iter()
+                        .map(|&c| self.cell(&self.items[*i], c, matched)),
+                ),
+            })
+            .collect();
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(theme.highlight())
+            .highlight_symbol(theme.icons.selected);
+        // 与列表布局共用选中位置与滚动偏移
+        let mut state = TableState::default()
+            .with_offset(self.state.offset())
+            .with_selected(self.state.selected());
+        f.render_stateful_widget(table, area, &mut state);
+        *self.state.offset_mut() = state.offset();
+    }
+
+    /// 表格中某一列的单元格
+    fn cell(&self, it: &DisplayItem, column: Column, matched: Style) -> Cell<'static> {
+        let theme = &self.theme;
+        let lang = self.lang;
+        match column {
+            Column::State => {
+                let icons = theme.icons;
+                let (icon, text, color) = if it.broken {
+                    (icons.broken, lang.pick("失效", "broken"), theme.error)
+                } else if it.disabled {
+                    (icons.disabled, lang.pick("禁用", "disabled"), theme.help)
+                } else {
+                    (icons.enabled, lang.pick("启用", "enabled"), theme.ok)
+                };
+                Cell::from(Span::styled(
+                    format!("{} {}", icon, text),
+                    Style::default().fg(color),
+                ))
+            }
+            Column::Source => Cell::from(Span::styled(
+                it.type_label.clone(),
+                Style::default().fg(theme.type_label),
+            )),
+            Column::Scope => Cell::from(it.scope.label(lang)),
+            Column::Name => {
+                let mut spans = vec![
+                    Span::styled(
+                        self.mark_of(it),
+                        Style::default().fg(theme.ok).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("{} ", theme.icons.source(it.source))),
+                ];
+                spans.extend(highlight(
+                    &it.label,
+                    &self.filter,
+                    Style::default().fg(theme.label),
+                    matched,
+                ));
+                Cell::from(Line::from(spans))
+            }
+            Column::Target => {
+                let target = it.target.as_deref().or(it.path.as_deref()).unwrap_or("-");
+                let color = if it.broken { theme.error } else { theme.path };
+                Cell::from(Line::from(highlight(
+                    target,
+                    &self.filter,
+                    Style::default().fg(color),
+                    matched,
+                )))
+            }
+            Column::Modified => Cell::from(Span::styled(
+                it.modified
+                    .map(helper::format_time)
+                    .unwrap_or_else(|| "-".to_string()),
+                Style::default().fg(theme.path),
+            )),
+            Column::Process => match &it.running {
+                Some(p) => Cell::from(Span::styled(
+                    format!("PID {}", p.pid),
+                    Style::default().fg(theme.ok),
+                )),
+                None => Cell::from(Span::styled("-", Style::default().fg(theme.path))),
+            },
+            Column::Trigger => {
+                let color = if it.runs_at_startup() {
+                    theme.label
+                } else {
+                    theme.info
+                };
+                Cell::from(Span::styled(
+                    it.trigger_summary(self.lang)
+                        .unwrap_or_else(|| "-".to_string()),
+                    Style::default().fg(color),
+                ))
+            }
+        }
+    }
+
+    /// 分组标题行：`▾ 标题 (项数)`，目录分组缩进一级
+    fn group_header(&self, row: &Row) -> Line<'static> {
+        let Row::Header {
+            title,
+            depth,
+            count,
+            collapsed,
+            ..
+        } = row
+        else {
+            return Line::default();
+        };
+        let icons = self.theme.icons;
+        let marker = if *collapsed {
+            icons.collapsed
         } else {
-            Color::Gray
+            icons.expanded
         };
-        let status = Paragraph::new(self.status.as_str()).style(Style::default().fg(status_color));
-        f.render_widget(status, chunks[2]);
+        let pad = "  ".repeat(*depth as usize);
+        let style = if *depth == 0 {
+            Style::default()
+                .fg(self.theme.header)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.theme.type_label)
+        };
+        Line::from(Span::styled(
+            format!("{}{} {} ({})", pad, marker, title, count),
+            style,
+        ))
+    }
 
-        // 帮助栏
-        let help = " ↑/↓ 或 j/k 移动 · d 删除 · r 刷新 · q 退出 ";
-        let help = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
-        f.render_widget(help, chunks[3]);
+    /// 标记位：有标记时多出一列，标记项显示标记图标
+    fn mark_of(&self, it: &DisplayItem) -> String {
+        match (self.marks.is_empty(), self.marks.contains(it)) {
+            (true, _) => String::new(),
+            (false, true) => format!("{} ", self.theme.icons.marked),
+            (false, false) => "  ".to_string(),
+        }
+    }
+
+    fn search_prompt(&self) -> Paragraph<'static> {
+        let lang = self.lang;
+        let mut spans = vec![
+            Span::styled("/", Style::default().fg(self.theme.header)),
+            Span::raw(self.filter.query().to_string()),
+            Span::styled("▏", Style::default().fg(self.theme.header)),
+        ];
+        let mode = if self.filter.is_regex() {
+            lang.pick("  [正则]", "  [regex]")
+        } else {
+            lang.pick("  [文本]", "  [text]")
+        };
+        spans.push(Span::styled(
+            mode,
+            Style::default().fg(self.theme.type_label),
+        ));
+        match self.filter.error() {
+            Some(_) => spans.push(Span::styled(
+                lang.pick(
+                    "  正则无效，按字面匹配",
+                    "  invalid regex, matching literally",
+                ),
+                Style::default().fg(self.theme.error),
+            )),
+            None => spans.push(Span::styled(
+                lang.pick(
+                    "  Enter 完成 · Esc 清除 · Ctrl+R 正则",
+                    "  Enter done · Esc clear · Ctrl+R regex",
+                ),
+                Style::default().fg(self.theme.help),
+            )),
+        }
+        Paragraph::new(Line::from(spans))
+    }
 
-        if self.confirm {
-            self.draw_confirm(f);
+    /// 绘制确认弹窗，返回 确认 / 取消 按钮的区域
+    fn draw_confirm(&self, f: &mut ratatui::Frame, batch: &Batch) -> (Rect, Rect) {
+        let lang = self.lang;
+        let count = batch.targets.len();
+        let question = match (batch.op, count) {
+            (Operation::Delete, 1) => lang
+                .pick("确认删除该启动项？", "Delete this startup item?")
+                .to_string(),
+            (Operation::Disable, 1) => lang
+                .pick("确认禁用该启动项？", "Disable this startup item?")
+                .to_string(),
+            (Operation::Delete, n) => tr!(
+                lang,
+                "确认删除以下 {} 个启动项？",
+                "Delete these {} startup items?",
+                n
+            ),
+            (Operation::Disable, n) => tr!(
+                lang,
+                "确认禁用以下 {} 个启动项？",
+                "Disable these {} startup items?",
+                n
+            ),
+        };
+        let question = format!("{} {}", self.theme.icons.warning, question);
+        let targets: Vec<Line> = batch
+            .targets
+            .iter()
+            .map(|&i| {
+                let it = &self.items[i];
+                let mut spans = vec![
+                    Span::raw(format!("{} ", self.theme.icons.source(it.source))),
+                    Span::styled(
+                        format!("[{}] {}", it.type_label, it.label),
+                        Style::default().fg(self.theme.type_label),
+                    ),
+                ];
+                if let Some(p) = &it.running {
+                    spans.push(Span::styled(
+                        format!(" [{}]", running_badge(p, lang)),
+                        Style::default().fg(self.theme.ok),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        // 按钮兼作按键提示
+        let confirm = Span::styled(
+            tr!(
+                lang,
+                "[ {} 确认 ]",
+                "[ {} Confirm ]",
+                self.keymap.describe_keys(Action::Confirm)
+            ),
+            Style::default()
+                .fg(self.theme.error)
+                .add_modifier(Modifier::BOLD),
+        );
+        let cancel = Span::styled(
+            lang.pick("[ 其它键 取消 ]", "[ Other keys: Cancel ]"),
+            Style::default().fg(self.theme.info),
+        );
+        let gap = 3;
+        let (confirm_width, cancel_width) = (confirm.width() as u16, cancel.width() as u16);
+        let mut head = vec![Line::from(Span::styled(
+            question,
+            Style::default()
+                .fg(self.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        // 有目标正在运行时，第二行显示是否同时结束进程
+        if self.any_running(batch) {
+            head.push(Line::from(Span::styled(
+                tr!(
+                    lang,
+                    "同时结束正在运行的进程: {}（{} 切换）",
+                    "Also terminate running instances: {} ({} to toggle)",
+                    if batch.terminate {
+                        lang.pick("是", "yes")
+                    } else {
+                        lang.pick("否", "no")
+                    },
+                    self.keymap.describe_keys(Action::ToggleTerminate)
+                ),
+                Style::default().fg(self.theme.info),
+            )));
         }
+        let question = Paragraph::new(head);
+        let list = Paragraph::new(targets).alignment(Alignment::Center);
+        let hint = Paragraph::new(Line::from(vec![
+            confirm,
+            Span::raw(" ".repeat(gap as usize)),
+            cancel,
+        ]));
+        let area =
+            self.draw_list_popup(f, lang.pick("确认", "Confirm"), question, list, hint, count);
+        // 与居中对齐的计算方式一致
+        let total = confirm_width + gap + cancel_width;
+        let x = area.x + area.width.saturating_sub(total) / 2;
+        (
+            Rect::new(x, area.y, confirm_width, 1).intersection(area),
+            Rect::new(x + confirm_width + gap, area.y, cancel_width, 1).intersection(area),
+        )
     }
 
-    fn draw_confirm(&self, f: &mut ratatui::Frame) {
-        let area = centered_rect(50, 7, f.size());
-        f.render_widget(Clear, area);
-        let i = self.state.selected().unwrap_or(0);
-        let label = self.items.get(i).map(|it| it.label.as_str()).unwrap_or("");
-        let lines = vec![
+    fn draw_report(&self, f: &mut ratatui::Frame, report: &Report) {
+        let lang = self.lang;
+        let count = report.outcomes.len();
+        let failed = report.outcomes.iter().filter(|(_, e)| e.is_some()).count();
+        let title = tr!(lang, "{}结果", "{} results", report.op.label(lang));
+        let summary = tr!(
+            lang,
+            "{} 项成功，{} 项失败",
+            "{} succeeded, {} failed",
+            count - failed,
+            failed
+        );
+        let lines: Vec<Line> = report
+            .outcomes
+            .iter()
+            .map(|(label, error)| match error {
+                None => Line::from(Span::styled(
+                    format!("{} {}", self.theme.icons.ok, label),
+                    Style::default().fg(self.theme.ok),
+                )),
+                Some(e) => Line::from(Span::styled(
+                    format!(
+                        "{} {}: {}",
+                        self.theme.icons.error,
+                        label,
+                        e.replace('\n', " ")
+                    ),
+                    Style::default().fg(self.theme.error),
+                )),
+            })
+            .collect();
+        let list = Paragraph::new(lines).wrap(Wrap { trim: false });
+        let hint = Paragraph::new(Span::styled(
+            lang.pick("任意键关闭", "Press any key to close"),
+            Style::default().fg(self.theme.info),
+        ));
+        self.draw_list_popup(f, &title, Paragraph::new(summary), list, hint, count);
+    }
+
+    fn draw_import(&self, f: &mut ratatui::Frame, import: &RegImport) {
+        let lang = self.lang;
+        let head = tr!(
+            lang,
+            "{} 导入编辑后的注册表值？",
+            "{} Import the edited registry value?",
+            self.theme.icons.warning
+        );
+        let list = Paragraph::new(vec![
             Line::from(Span::styled(
-                "⚠  确认删除该启动项？",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                import.label.clone(),
+                Style::default().fg(self.theme.type_label),
             )),
-            Line::from(""),
             Line::from(Span::styled(
-                label.to_string(),
-                Style::default().fg(Color::Yellow),
+                import.path.display().to_string(),
+                Style::default().fg(self.theme.path),
             )),
-            Line::from(""),
+        ]);
+        let hint = Paragraph::new(Span::styled(
+            tr!(
+                lang,
+                "{} 导入 · 其它键放弃",
+                "{} import · other keys discard",
+                self.keymap.describe_keys(Action::Confirm)
+            ),
+            Style::default().fg(self.theme.info),
+        ));
+        self.draw_list_popup(
+            f,
+            lang.pick("导入注册表", "Registry import"),
+            Paragraph::new(head),
+            list,
+            hint,
+            2,
+        );
+    }
+
+    /// 日志查看器：覆盖列表区域，依次为各日志文件（读取失败时附原因）、日志内容与提示行
+    fn draw_logs(&self, f: &mut ratatui::Frame, area: Rect, view: &mut LogView) {
+        let lang = self.lang;
+        let theme = &self.theme;
+        let position = if view.scroll.is_none() {
+            lang.pick("跟随中", "following").to_string()
+        } else {
+            format!("{}/{}", view.top() + 1, view.lines().len())
+        };
+        let block = Block::default().borders(Borders::ALL).title(tr!(
+            lang,
+            "日志: {}（{}）",
+            "Logs: {} ({})",
+            view.title,
+            position
+        ));
+        let chunks = Layout::vertical([
+            Constraint::Length(view.file_count() as u16),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(block.inner(area));
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+
+        let files: Vec<Line> = view
+            .files()
+            .map(|(path, error)| match error {
+                Some(e) => Line::from(Span::styled(
+                    format!("{} {}: {}", theme.icons.error, path.display(), e),
+                    Style::default().fg(theme.error),
+                )),
+                None => Line::from(Span::styled(
+                    path.display().to_string(),
+                    Style::default().fg(theme.path),
+                )),
+            })
+            .collect();
+        f.render_widget(Paragraph::new(files), chunks[0]);
+
+        view.height = chunks[1].height as usize;
+        let matched = Style::default()
+            .fg(theme.matched)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let names: Vec<String> = view
+            .files()
+            .map(|(path, _)| {
+                path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |n| n.to_string_lossy().into_owned(),
+                )
+            })
+            .collect();
+        let lines: Vec<Line> = if view.lines().is_empty() {
+            vec![Line::from(Span::styled(
+                lang.pick(
+                    "（暂无内容，新写入的内容会自动显示）",
+                    "(empty, new output appears here)",
+                ),
+                Style::default().fg(theme.info),
+            ))]
+        } else {
+            view.lines()
+                .iter()
+                .skip(view.top())
+                .take(view.height)
+                .map(|(file, text)| {
+                    let mut spans = vec![];
+                    // 多个文件时标出每行的来源文件
+                    if names.len() > 1 {
+                        spans.push(Span::styled(
+                            format!("[{}] ", names[*file]),
+                            Style::default().fg(theme.type_label),
+                        ));
+                    }
+                    spans.extend(highlight(
+                        text,
+                        &view.filter,
+                        Style::default().fg(theme.label),
+                        matched,
+                    ));
+                    Line::from(spans)
+                })
+                .collect()
+        };
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+
+        let hint = if view.searching {
+            Line::from(vec![
+                Span::styled("/", Style::default().fg(theme.header)),
+                Span::raw(view.filter.query().to_string()),
+                Span::styled("▏", Style::default().fg(theme.header)),
+                Span::styled(
+                    tr!(
+                        lang,
+                        "  {} 个匹配 · Enter 完成 · Esc 清除 · Ctrl+R 正则",
+                        "  {} matches · Enter done · Esc clear · Ctrl+R regex",
+                        view.match_count()
+                    ),
+                    Style::default().fg(theme.help),
+                ),
+            ])
+        } else {
             Line::from(Span::styled(
-                "y 确认  /  其它键取消",
-                Style::default().fg(Color::Gray),
-            )),
+                tr!(
+                    lang,
+                    "{} 滚动 · {} 跟随 · {} 搜索 · {} 匹配 · {} 关闭",
+                    "{} scroll · {} follow · {} search · {} matches · {} close",
+                    format!(
+                        "{}/{}",
+                        self.keymap.describe_keys(Action::MoveDown),
+                        self.keymap.describe_keys(Action::MoveUp)
+                    ),
+                    self.keymap.describe_keys(Action::MoveBottom),
+                    self.keymap.describe_keys(Action::Search),
+                    format!(
+                        "{}/{}",
+                        self.keymap.describe_keys(Action::NextMatch),
+                        self.keymap.describe_keys(Action::PrevMatch)
+                    ),
+                    self.keymap.describe_keys(Action::ClearFilter)
+                ),
+                Style::default().fg(theme.help),
+            ))
+        };
+        f.render_widget(Paragraph::new(hint), chunks[2]);
+    }
+
+    /// 绘制 说明 / 可滚动列表 / 提示 三段式弹窗，高度随列表行数增长（不超过屏幕）；
+    /// 返回提示行的区域
+    fn draw_list_popup(
+        &self,
+        f: &mut ratatui::Frame,
+        title: &str,
+        head: Paragraph,
+        list: Paragraph,
+        hint: Paragraph,
+        rows: usize,
+    ) -> Rect {
+        let height = (rows as u16).saturating_add(6).min(f.size().height);
+        let area = centered_rect(60, height, f.size());
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string());
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(block.inner(area));
+        f.render_widget(block, area);
+        f.render_widget(head.alignment(Alignment::Center), chunks[0]);
+        f.render_widget(
+            list.scroll((self.popup_scroll.min(rows as u16), 0)),
+            chunks[1],
+        );
+        f.render_widget(hint.alignment(Alignment::Center), chunks[2]);
+        chunks[2]
+    }
+
+    fn draw_columns(&self, f: &mut ratatui::Frame, cursor: usize) {
+        let lang = self.lang;
+        let lines: Vec<Line> = self
+            .layout
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let text = format!(
+                    "{} [{}] {:<16} {:>3}",
+                    if i == cursor { ">" } else { " " },
+                    if c.visible { "x" } else { " " },
+                    c.column.title(lang),
+                    c.width
+                );
+                let style = if i == cursor {
+                    self.theme.highlight()
+                } else {
+                    Style::default()
+                };
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+        let head = Paragraph::new(if self.layout.table {
+            lang.pick("当前为表格布局", "Table layout active")
+        } else {
+            lang.pick(
+                "当前为列表布局，表格中生效",
+                "List layout active, applies to table",
+            )
+        });
+        let hint = Paragraph::new(Span::styled(
+            tr!(
+                lang,
+                "{} 显示/隐藏 · {} / {} 调整宽度 · {} / {} 移动 · 其它键关闭",
+                "{} show/hide · {} / {} width · {} / {} move · other keys close",
+                self.keymap.describe_keys(Action::ToggleMark),
+                self.keymap.describe_keys(Action::Collapse),
+                self.keymap.describe_keys(Action::Expand),
+                self.keymap.describe_keys(Action::MoveColumnDown),
+                self.keymap.describe_keys(Action::MoveColumnUp)
+            ),
+            Style::default().fg(self.theme.info),
+        ));
+        self.draw_list_popup(
+            f,
+            lang.pick("列设置", "Columns"),
+            head,
+            Paragraph::new(lines),
+            hint,
+            self.layout.columns.len(),
+        );
+    }
+
+    /// 详情面板：选中启动项的全部字段；选中分组标题时显示分组信息
+    fn draw_details(&self, f: &mut ratatui::Frame, area: Rect) {
+        let lang = self.lang;
+        let theme = &self.theme;
+        let field = |name: &str, value: String| {
+            vec![
+                Line::from(Span::styled(
+                    name.to_string(),
+                    Style::default().fg(theme.type_label),
+                )),
+                Line::from(Span::styled(
+                    format!("  {}", value),
+                    Style::default().fg(theme.label),
+                )),
+            ]
+        };
+        let or_dash = |value: Option<&str>| value.unwrap_or("-").to_string();
+        let lines: Vec<Line> = match self.state.selected().and_then(|i| self.rows.get(i)) {
+            Some(Row::Item(i)) => {
+                let it = &self.items[*i];
+                let state = if it.broken {
+                    lang.pick("失效（可执行文件不存在）", "broken (executable missing)")
+                } else if it.disabled {
+                    lang.pick("已禁用", "disabled")
+                } else {
+                    lang.pick("启用", "enabled")
+                };
+                let mut state = if it.protected {
+                    tr!(
+                        lang,
+                        "{}，受系统保护（只读）",
+                        "{}, system-protected (read-only)",
+                        state
+                    )
+                } else {
+                    state.to_string()
+                };
+                if it.hidden {
+                    state += lang.pick("，登录时隐藏", ", hidden at login");
+                }
+                if it.unlisted {
+                    state += lang.pick("，任务管理器不显示", ", not shown in Task Manager");
+                }
+                let source = format!("{} {}", theme.icons.source(it.source), it.type_label);
+                let source = match &it.kind {
+                    Some(kind) => format!("{} ({})", source, kind),
+                    None => source,
+                };
+                [
+                    field(lang.pick("名称", "Name"), it.label.clone()),
+                    field(lang.pick("来源", "Source"), source),
+                    field(lang.pick("范围", "Scope"), it.scope.label(lang).to_string()),
+                    field(
+                        lang.pick("开发者", "Developer"),
+                        or_dash(it.developer.as_deref()),
+                    ),
+                    field(
+                        lang.pick("运行身份", "Run as"),
+                        or_dash(it.principal.as_deref()),
+                    ),
+                    field(lang.pick("状态", "State"), state),
+                    field(
+                        lang.pick("触发", "Trigger"),
+                        it.trigger_summary(lang).unwrap_or_else(|| {
+                            lang.pick("开机 / 登录时", "at boot / login").to_string()
+                        }),
+                    ),
+                    field(
+                        lang.pick("进程", "Process"),
+                        it.running.as_ref().map_or_else(
+                            || lang.pick("未运行", "not running").to_string(),
+                            |p| process_details(p, lang),
+                        ),
+                    ),
+                    field(lang.pick("路径", "Path"), or_dash(it.path.as_deref())),
+                    field(lang.pick("命令", "Command"), or_dash(it.command.as_deref())),
+                    field(lang.pick("目标", "Target"), or_dash(it.target.as_deref())),
+                    field(
+                        lang.pick("位置", "Location"),
+                        or_dash(it.location.as_deref()),
+                    ),
+                    field(
+                        lang.pick("修改时间 (UTC)", "Modified (UTC)"),
+                        it.modified
+                            .map(helper::format_time)
+                            .unwrap_or_else(|| "-".to_string()),
+                    ),
+                ]
+                .concat()
+            }
+            Some(Row::Header { title, count, .. }) => [
+                field(lang.pick("分组", "Group"), title.clone()),
+                field(lang.pick("项数", "Items"), count.to_string()),
+            ]
+            .concat(),
+            None => vec![Line::from(lang.pick("未选中启动项", "Nothing selected"))],
+        };
+        let details = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(lang.pick("详情", "Details")),
+        );
+        f.render_widget(details, area);
+    }
+
+    /// 帮助弹窗：按分类列出全部动作的当前按键与说明，以及不走键位映射的固定按键。
+    /// 返回最大滚动位置
+    fn draw_help(&self, f: &mut ratatui::Frame) -> u16 {
+        let lang = self.lang;
+        let heading = Style::default()
+            .fg(self.theme.header)
+            .add_modifier(Modifier::BOLD);
+        let entry = |keys: String, help: &str| {
+            let pad = 16usize.saturating_sub(Span::raw(keys.as_str()).width());
+            Line::from(vec![
+                Span::styled(
+                    format!("  {}{}", keys, " ".repeat(pad)),
+                    Style::default().fg(self.theme.type_label),
+                ),
+                Span::raw(help.to_string()),
+            ])
+        };
+        let mut lines = vec![];
+        for (category, entries) in self.keymap.help_sections(lang) {
+            lines.push(Line::from(Span::styled(category.label(lang), heading)));
+            lines.extend(entries.into_iter().map(|(keys, help)| entry(keys, help)));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            lang.pick("固定按键", "Fixed keys"),
+            heading,
+        )));
+        let fixed = [
+            (
+                "1–9",
+                lang.pick("跳到第 n 个标签页（1 为全部）", "Jump to tab n (1 is All)"),
+            ),
+            (
+                "Enter",
+                lang.pick("搜索时：完成输入", "While searching: done"),
+            ),
+            (
+                "Esc",
+                lang.pick("搜索时：清除关键字", "While searching: clear"),
+            ),
+            (
+                "Ctrl+R",
+                lang.pick("搜索时：切换正则", "While searching: toggle regex"),
+            ),
+            (
+                "Ctrl+U",
+                lang.pick("搜索时：清空输入", "While searching: clear input"),
+            ),
         ];
+        lines.extend(
+            fixed
+                .iter()
+                .map(|(keys, help)| entry(keys.to_string(), help)),
+        );
+
+        let height = (lines.len() as u16 + 2).min(f.size().height);
+        let area = centered_rect(70, height, f.size());
+        f.render_widget(Clear, area);
+        // 滚动到底时最后一行贴住下边框
+        let max_scroll = (lines.len() as u16).saturating_sub(height.saturating_sub(2));
+        let title = tr!(
+            lang,
+            "帮助 · {} 滚动 · 其它键关闭",
+            "Help · {} scroll · other keys close",
+            format!(
+                "{}/{}",
+                self.keymap.describe_keys(Action::MoveDown),
+                self.keymap.describe_keys(Action::MoveUp)
+            )
+        );
         let popup = Paragraph::new(lines)
-            .block(Block::default().borders(Borders::ALL).title("确认"))
-            .alignment(Alignment::Center);
+            .scroll((self.popup_scroll.min(max_scroll), 0))
+            .block(Block::default().borders(Borders::ALL).title(title));
         f.render_widget(popup, area);
+        max_scroll
     }
+
+    fn draw_diagnostics(&self, f: &mut ratatui::Frame) {
+        let lang = self.lang;
+        let height = (self.diagnostics.len() as u16 * 2 + 2).clamp(4, f.size().height);
+        let area = centered_rect(80, height, f.size());
+        f.render_widget(Clear, area);
+        let lines: Vec<Line> = if self.diagnostics.is_empty() {
+            vec![Line::from(lang.pick("没有诊断信息", "No diagnostics"))]
+        } else {
+            self.diagnostics
+                .iter()
+                .map(|d| {
+                    Line::from(Span::styled(
+                        d.to_string(),
+                        Style::default().fg(self.theme.error),
+                    ))
+                })
+                .collect()
+        };
+        let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(lang.pick("诊断", "Diagnostics")),
+        );
+        f.render_widget(popup, area);
+    }
+}
+
+/// 把文本按搜索命中位置切分成普通 / 高亮两种样式的片段
+fn highlight(text: &str, filter: &Filter, base: Style, matched: Style) -> Vec<Span<'static>> {
+    match filter.find(text) {
+        Some(range) if !range.is_empty() => vec![
+            Span::styled(text[..range.start].to_string(), base),
+            Span::styled(text[range.clone()].to_string(), matched),
+            Span::styled(text[range.end..].to_string(), base),
+        ],
+        _ => vec![Span::styled(text.to_string(), base)],
+    }
+}
+
+/// 离线模式的标题后缀，如 `  [离线只读: /mnt/win (WIN-PC)]`
+fn offline_title(root: &Path, name: Option<&str>, lang: Lang) -> String {
+    let root = root.display();
+    match name {
+        Some(name) => tr!(
+            lang,
+            "  [离线只读: {} ({})]",
+            "  [offline, read-only: {} ({})]",
+            root,
+            name
+        ),
+        None => tr!(lang, "  [离线只读: {}]", "  [offline, read-only: {}]", root),
+    }
+}
+
+/// 运行状态标记，如 `运行中 PID 123`
+fn running_badge(p: &ProcessInfo, lang: Lang) -> String {
+    tr!(lang, "运行中 PID {}", "running PID {}", p.pid)
+}
+
+/// 详情面板中的进程信息：PID、CPU 时间与内存，无法读取的项显示 `-`
+fn process_details(p: &ProcessInfo, lang: Lang) -> String {
+    let cpu = p
+        .cpu_time
+        .map(process::format_cpu_time)
+        .unwrap_or_else(|| "-".to_string());
+    let memory = p
+        .memory
+        .map(process::format_bytes)
+        .unwrap_or_else(|| "-".to_string());
+    tr!(
+        lang,
+        "PID {} · CPU {} · 内存 {}",
+        "PID {} · CPU {} · memory {}",
+        p.pid,
+        cpu,
+        memory
+    )
+}
+
+/// 进程快照中与启动项的目标和参数一致的进程
+fn find_process<'a>(
+    processes: &'a [process::ProcessInfo],
+    item: &DisplayItem,
+) -> Option<&'a process::ProcessInfo> {
+    let args = item
+        .command
+        .as_deref()
+        .map(helper::arguments_of)
+        .unwrap_or_default();
+    process::find(processes, item.target.as_deref()?, &args)
+}
+
+fn contains(area: Rect, x: u16, y: u16) -> bool {
+    x >= area.x && x < area.right() && y >= area.y && y < area.bottom()
+}
+
+/// 编辑器命令：依次取 `$VISUAL`、`$EDITOR`，都未设置时 Windows 用 `notepad`，其余用 `vi`
+fn editor_command(path: &std::path::Path) -> Command {
+    let configured = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| helper::split_command_line(&value))
+        .find(|args| !args.is_empty());
+    let args = configured.unwrap_or_else(|| {
+        let default = if cfg!(windows) { "notepad" } else { "vi" };
+        vec![default.to_string()]
+    });
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]).arg(path);
+    command
 }
 
 /// 计算居中矩形区域，用于弹窗
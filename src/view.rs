@@ -0,0 +1,573 @@
+use crate::i18n::Lang;
+use crate::platform::helper::DisplayItem;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// 列表排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// 扫描顺序（各来源依次排列）
+    #[default]
+    Scan,
+    /// 按名称
+    Name,
+    /// 按来源类型，再按名称
+    Source,
+    /// 按作用范围（用户在前），再按名称
+    Scope,
+    /// 按修改时间，最新的在前
+    Modified,
+    /// 失效项在前，再按名称
+    Broken,
+}
+
+impl SortMode {
+    const ALL: &'static [SortMode] = &[
+        SortMode::Scan,
+        SortMode::Name,
+        SortMode::Source,
+        SortMode::Scope,
+        SortMode::Modified,
+        SortMode::Broken,
+    ];
+
+    /// 循环切换到下一种排序方式
+    pub fn next(self) -> SortMode {
+        let i = SortMode::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        SortMode::ALL[(i + 1) % SortMode::ALL.len()]
+    }
+
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            SortMode::Scan => lang.pick("扫描顺序", "scan order"),
+            SortMode::Name => lang.pick("名称", "name"),
+            SortMode::Source => lang.pick("来源", "source"),
+            SortMode::Scope => lang.pick("范围", "scope"),
+            SortMode::Modified => lang.pick("修改时间", "modified"),
+            SortMode::Broken => lang.pick("失效优先", "broken first"),
+        }
+    }
+}
+
+/// 名称比较：忽略大小写
+fn by_label(a: &DisplayItem, b: &DisplayItem) -> Ordering {
+    a.label.to_lowercase().cmp(&b.label.to_lowercase())
+}
+
+/// 搜索过滤条件：对名称、路径与命令做忽略大小写的匹配，可选正则
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    query: String,
+    regex: bool,
+    /// 编译后的匹配器；关键字为空时为 `None`（匹配全部）
+    matcher: Option<Regex>,
+    /// 正则无效时的错误信息，此时退化为按字面匹配
+    error: Option<String>,
+}
+
+impl Filter {
+    pub fn new(query: &str, regex: bool) -> Filter {
+        let mut filter = Filter {
+            query: query.to_string(),
+            regex,
+            matcher: None,
+            error: None,
+        };
+        filter.compile();
+        filter
+    }
+
+    fn compile(&mut self) {
+        self.error = None;
+        self.matcher = None;
+        if self.query.is_empty() {
+            return;
+        }
+        let literal = || regex::escape(&self.query);
+        let pattern = if self.regex {
+            self.query.clone()
+        } else {
+            literal()
+        };
+        let build = |p: &str| RegexBuilder::new(p).case_insensitive(true).build();
+        self.matcher = match build(&pattern) {
+            Ok(re) => Some(re),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                build(&literal()).ok()
+            }
+        };
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.regex
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.compile();
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.compile();
+    }
+
+    /// 文本中第一个匹配的字节范围，用于高亮
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        self.matcher.as_ref()?.find(text).map(|m| m.range())
+    }
+
+    /// 名称、路径或命令任一匹配；空关键字匹配全部
+    pub fn matches(&self, item: &DisplayItem) -> bool {
+        let Some(re) = &self.matcher else {
+            return true;
+        };
+        re.is_match(&item.label)
+            || item.path.as_deref().is_some_and(|p| re.is_match(p))
+            || item.command.as_deref().is_some_and(|c| re.is_match(c))
+    }
+}
+
+/// 按过滤与排序计算可见项，返回 `items` 中的下标
+pub fn arrange(items: &[DisplayItem], sort: SortMode, filter: &Filter) -> Vec<usize> {
+    let mut visible: Vec<usize> = (0..items.len())
+        .filter(|&i| filter.matches(&items[i]))
+        .collect();
+    // 均为稳定排序，次序相同的项保持扫描顺序
+    let by = |key: fn(&DisplayItem, &DisplayItem) -> Ordering| {
+        move |&a: &usize, &b: &usize| key(&items[a], &items[b])
+    };
+    match sort {
+        SortMode::Scan => {}
+        SortMode::Name => visible.sort_by(by(by_label)),
+        SortMode::Source => visible.sort_by(by(|a, b| {
+            a.type_label.cmp(&b.type_label).then_with(|| by_label(a, b))
+        })),
+        SortMode::Scope => visible.sort_by(by(|a, b| {
+            a.scope.cmp(&b.scope).then_with(|| by_label(a, b))
+        })),
+        // 无修改时间的项排在最后
+        SortMode::Modified => visible.sort_by_key(|&i| Reverse(items[i].modified)),
+        SortMode::Broken => visible.sort_by(by(|a, b| {
+            b.broken.cmp(&a.broken).then_with(|| by_label(a, b))
+        })),
+    }
+    visible
+}
+
+/// 来源标签页：按来源在 `items` 中首次出现的顺序，返回 `(来源, 可见项数)`。
+/// 没有可见项的来源也保留，避免过滤时标签页跳动
+pub fn tabs(items: &[DisplayItem], visible: &[usize]) -> Vec<(String, usize)> {
+    let mut tabs: Vec<(String, usize)> = vec![];
+    for item in items {
+        if !tabs.iter().any(|(source, _)| *source == item.type_label) {
+            tabs.push((item.type_label.clone(), 0));
+        }
+    }
+    for &i in visible {
+        if let Some(tab) = tabs.iter_mut().find(|(s, _)| *s == items[i].type_label) {
+            tab.1 += 1;
+        }
+    }
+    tabs
+}
+
+/// 分组视图中的一行：分组标题或启动项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Row {
+    Header {
+        /// 分组标识，用于记录折叠状态：来源为 `<来源>`，目录为 `<来源>\n<目录>`
+        key: String,
+        title: String,
+        /// 0 为来源分组，1 为目录分组
+        depth: u8,
+        count: usize,
+        collapsed: bool,
+    },
+    /// `items` 中的下标
+    Item(usize),
+}
+
+/// 启动项所在的分组标识：`(来源分组, 目录分组)`
+pub fn group_keys(item: &DisplayItem) -> (String, String) {
+    let source = item.type_label.clone();
+    let dir = format!("{}\n{}", source, item.location.as_deref().unwrap_or("-"));
+    (source, dir)
+}
+
+/// 同一来源下按目录划分的项：`(目录, items 下标)`
+type DirGroups<'a> = Vec<(&'a str, Vec<usize>)>;
+
+/// 把可见项组织为行；不分组时每项一行
+pub fn rows(
+    items: &[DisplayItem],
+    visible: &[usize],
+    grouped: bool,
+    collapsed: &BTreeSet<String>,
+) -> Vec<Row> {
+    if !grouped {
+        return visible.iter().map(|&i| Row::Item(i)).collect();
+    }
+
+    // 来源 → 目录 → 项，均按首次出现的顺序，组内保持当前排序
+    let mut groups: Vec<(&str, DirGroups)> = vec![];
+    for &i in visible {
+        let item = &items[i];
+        let source = item.type_label.as_str();
+        let dir = item.location.as_deref().unwrap_or("-");
+        let pos = match groups.iter().position(|(s, _)| *s == source) {
+            Some(pos) => pos,
+            None => {
+                groups.push((source, vec![]));
+                groups.len() - 1
+            }
+        };
+        let dirs = &mut groups[pos].1;
+        match dirs.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, members)) => members.push(i),
+            None => dirs.push((dir, vec![i])),
+        }
+    }
+
+    let mut rows = vec![];
+    for (source, dirs) in groups {
+        let key = source.to_string();
+        let folded = collapsed.contains(&key);
+        rows.push(Row::Header {
+            title: source.to_string(),
+            depth: 0,
+            count: dirs.iter().map(|(_, m)| m.len()).sum(),
+            collapsed: folded,
+            key,
+        });
+        if folded {
+            continue;
+        }
+        for (dir, members) in dirs {
+            let key = group_keys(&items[members[0]]).1;
+            let folded = collapsed.contains(&key);
+            rows.push(Row::Header {
+                title: dir.to_string(),
+                depth: 1,
+                count: members.len(),
+                collapsed: folded,
+                key,
+            });
+            if !folded {
+                rows.extend(members.into_iter().map(Row::Item));
+            }
+        }
+    }
+    rows
+}
+
+/// 多选标记。按删除令牌记录，刷新或删除其它项后仍对应同一启动项
+#[derive(Debug, Default)]
+pub struct Marks(BTreeSet<String>);
+
+impl Marks {
+    fn key(item: &DisplayItem) -> &str {
+        &item.option.value
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, item: &DisplayItem) -> bool {
+        self.0.contains(Self::key(item))
+    }
+
+    pub fn toggle(&mut self, item: &DisplayItem) {
+        if !self.0.remove(Self::key(item)) {
+            self.0.insert(Self::key(item).to_string());
+        }
+    }
+
+    pub fn unmark(&mut self, item: &DisplayItem) {
+        self.0.remove(Self::key(item));
+    }
+
+    /// 标记全部可见项（保留已标记的隐藏项）
+    pub fn mark_all(&mut self, items: &[DisplayItem], visible: &[usize]) {
+        for &i in visible {
+            self.0.insert(Self::key(&items[i]).to_string());
+        }
+    }
+
+    /// 反选可见项，隐藏项的标记不变
+    pub fn invert(&mut self, items: &[DisplayItem], visible: &[usize]) {
+        for &i in visible {
+            self.toggle(&items[i]);
+        }
+    }
+
+    /// 丢弃已不存在的启动项的标记（如刷新后）
+    pub fn retain_existing(&mut self, items: &[DisplayItem]) {
+        let existing: BTreeSet<&str> = items.iter().map(Self::key).collect();
+        self.0.retain(|key| existing.contains(key.as_str()));
+    }
+
+    /// 已标记项在 `items` 中的下标，按扫描顺序
+    pub fn targets(&self, items: &[DisplayItem]) -> Vec<usize> {
+        (0..items.len())
+            .filter(|&i| self.contains(&items[i]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::helper::{OptionItem, Scope};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn item(type_label: &str, label: &str, path: Option<&str>) -> DisplayItem {
+        DisplayItem {
+            source: "test",
+            type_label: type_label.to_string(),
+            label: label.to_string(),
+            path: path.map(str::to_string),
+            command: None,
+            scope: Scope::System,
+            location: path
+                .and_then(|p| p.rsplit_once('/'))
+                .map(|(dir, _)| dir.to_string()),
+            modified: None,
+            target: None,
+            broken: false,
+            disabled: false,
+            running: None,
+            triggers: vec![],
+            protected: false,
+            developer: None,
+            hidden: false,
+            kind: None,
+            unlisted: false,
+            principal: None,
+            option: OptionItem {
+                label: label.to_string(),
+                value: format!("test|{}", label),
+            },
+        }
+    }
+
+    fn sample() -> Vec<DisplayItem> {
+        vec![
+            item(
+                "Plist",
+                "com.zoom.agent.plist",
+                Some("/Library/LaunchAgents/z"),
+            ),
+            item("Plist", "com.Adobe.plist", Some("/Library/LaunchDaemons/a")),
+            item("Login Item", "Dropbox", None),
+        ]
+    }
+
+    fn all() -> Filter {
+        Filter::default()
+    }
+
+    #[test]
+    fn scan_order_is_kept() {
+        assert_eq!(arrange(&sample(), SortMode::Scan, &all()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn sort_by_name_ignores_case() {
+        assert_eq!(arrange(&sample(), SortMode::Name, &all()), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn sort_by_source_then_name() {
+        assert_eq!(arrange(&sample(), SortMode::Source, &all()), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn filter_matches_label_path_or_command() {
+        let mut items = sample();
+        items[2].command = Some("/Applications/Dropbox.app --startup".to_string());
+        let arrange_with = |q: &str| arrange(&items, SortMode::Scan, &Filter::new(q, false));
+        assert_eq!(arrange_with("ADOBE"), vec![1]);
+        assert_eq!(arrange_with("launchagents"), vec![0]);
+        assert_eq!(arrange_with("--startup"), vec![2]);
+        assert!(arrange_with("nothing").is_empty());
+    }
+
+    #[test]
+    fn literal_query_escapes_regex_syntax() {
+        let filter = Filter::new("a.plist", false);
+        assert!(filter.find("com.a.plist").is_some());
+        assert!(filter.find("com.abplist").is_none());
+    }
+
+    #[test]
+    fn regex_query_is_case_insensitive() {
+        let filter = Filter::new(r"^com\.(zoom|adobe)", true);
+        assert_eq!(arrange(&sample(), SortMode::Scan, &filter), vec![0, 1]);
+        assert_eq!(filter.find("COM.Adobe.plist"), Some(0..9));
+    }
+
+    #[test]
+    fn invalid_regex_falls_back_to_literal() {
+        let filter = Filter::new("(", true);
+        assert!(filter.error().is_some());
+        assert!(filter.find("a(b").is_some());
+        assert!(filter.find("ab").is_none());
+    }
+
+    #[test]
+    fn toggle_regex_recompiles() {
+        let mut filter = Filter::new("a.b", false);
+        assert!(filter.find("axb").is_none());
+        filter.toggle_regex();
+        assert!(filter.find("axb").is_some());
+    }
+
+    #[test]
+    fn sort_by_scope_modified_and_broken() {
+        let mut items = sample();
+        items[2].scope = Scope::User;
+        items[0].modified = Some(UNIX_EPOCH + Duration::from_secs(10));
+        items[1].modified = Some(UNIX_EPOCH + Duration::from_secs(20));
+        items[0].broken = true;
+        assert_eq!(arrange(&items, SortMode::Scope, &all()), vec![2, 1, 0]);
+        assert_eq!(arrange(&items, SortMode::Modified, &all()), vec![1, 0, 2]);
+        assert_eq!(arrange(&items, SortMode::Broken, &all()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn sort_mode_cycles() {
+        let mut mode = SortMode::Scan;
+        for _ in 0..SortMode::ALL.len() {
+            mode = mode.next();
+        }
+        assert_eq!(mode, SortMode::Scan);
+    }
+
+    #[test]
+    fn ungrouped_rows_are_items() {
+        let items = sample();
+        let rows = rows(&items, &[2, 0], false, &BTreeSet::new());
+        assert_eq!(rows, vec![Row::Item(2), Row::Item(0)]);
+    }
+
+    #[test]
+    fn grouped_rows_have_source_and_dir_headers() {
+        let items = sample();
+        let rows = rows(&items, &[0, 1, 2], true, &BTreeSet::new());
+        let headers: Vec<(u8, String, usize)> = rows
+            .iter()
+            .filter_map(|r| match r {
+                Row::Header {
+                    depth,
+                    title,
+                    count,
+                    ..
+                } => Some((*depth, title.clone(), *count)),
+                Row::Item(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                (0, "Plist".to_string(), 2),
+                (1, "/Library/LaunchAgents".to_string(), 1),
+                (1, "/Library/LaunchDaemons".to_string(), 1),
+                (0, "Login Item".to_string(), 1),
+                (1, "-".to_string(), 1),
+            ]
+        );
+        assert_eq!(rows.len(), 8);
+    }
+
+    #[test]
+    fn collapsed_groups_hide_members() {
+        let items = sample();
+        let collapsed: BTreeSet<String> = ["Plist\n/Library/LaunchDaemons".to_string()].into();
+        let folded = rows(&items, &[0, 1, 2], true, &collapsed);
+        assert!(!folded.contains(&Row::Item(1)));
+        assert!(folded.contains(&Row::Item(0)));
+
+        let collapsed: BTreeSet<String> = ["Plist".to_string()].into();
+        let folded = rows(&items, &[0, 1, 2], true, &collapsed);
+        // 折叠的来源标题 + Login Item 的来源与目录标题 + Dropbox
+        assert_eq!(folded.len(), 4);
+        assert!(matches!(
+            &folded[0],
+            Row::Header {
+                collapsed: true,
+                count: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn marks_follow_items_not_positions() {
+        let mut items = sample();
+        let mut marks = Marks::default();
+        marks.toggle(&items[1]);
+        marks.toggle(&items[2]);
+        assert_eq!(marks.targets(&items), vec![1, 2]);
+        items.remove(0);
+        assert_eq!(marks.targets(&items), vec![0, 1]);
+        marks.toggle(&items[0]);
+        assert_eq!(marks.targets(&items), vec![1]);
+    }
+
+    #[test]
+    fn mark_all_and_invert_only_touch_visible() {
+        let items = sample();
+        let mut marks = Marks::default();
+        marks.toggle(&items[2]);
+        marks.mark_all(&items, &[0]);
+        assert_eq!(marks.targets(&items), vec![0, 2]);
+        marks.invert(&items, &[0, 1]);
+        assert_eq!(marks.targets(&items), vec![1, 2]);
+    }
+
+    #[test]
+    fn stale_marks_are_dropped() {
+        let mut items = sample();
+        let mut marks = Marks::default();
+        marks.mark_all(&items, &[0, 1, 2]);
+        items.truncate(1);
+        marks.retain_existing(&items);
+        assert_eq!(marks.len(), 1);
+    }
+
+    #[test]
+    fn tabs_keep_sources_without_matches() {
+        let items = sample();
+        assert_eq!(
+            tabs(&items, &[0, 1, 2]),
+            vec![("Plist".to_string(), 2), ("Login Item".to_string(), 1)]
+        );
+        assert_eq!(
+            tabs(&items, &[2]),
+            vec![("Plist".to_string(), 0), ("Login Item".to_string(), 1)]
+        );
+    }
+}